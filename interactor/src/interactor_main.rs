@@ -36,6 +36,7 @@ pub struct GameSettingsMock {
     pub number_of_players_min: u64, //min and max
     pub number_of_players_max: u64,
     pub wager: u64,
+    pub token_id: String,
     pub creator: Bech32Address,
     pub status: proxy::Status,
 }
@@ -59,8 +60,9 @@ async fn main() {
         // "isUserAdmin" => interact.is_user_admin().await,
         // "getLastGameId" => interact.last_game_id().await,
         // "getGameSettings" => interact.game_settings().await,
-        // "getGameIdBySettings" => interact.game_id().await,
+        // "getGameIdByCreator" => interact.game_id_by_creator().await,
         // "getPlayers" => interact.players().await,
+        // "getGameInfo" => interact.game_info().await,
         // "getGamesPerUser" => interact.games_per_user().await,
         // "sendReward" => interact.send_reward().await,
         // "enableSC" => interact.enable_sc().await,
@@ -273,6 +275,8 @@ impl ContractInteract {
                 number_of_players_min,
                 number_of_players_max,
                 wager,
+                OptionalValue::<u64>::None,
+                OptionalValue::<u64>::None,
             )
             .payment((TokenIdentifier::from(token_id), token_nonce, token_amount))
             .returns(ReturnsResultUnmanaged)
@@ -310,6 +314,8 @@ impl ContractInteract {
                 number_of_players_min,
                 number_of_players_max,
                 wager,
+                OptionalValue::<u64>::None,
+                OptionalValue::<u64>::None,
             )
             .payment((TokenIdentifier::from(token_id), token_nonce, token_amount))
             .returns(expected_result)
@@ -501,29 +507,29 @@ impl ContractInteract {
             number_of_players_min: result_value.number_of_players_min,
             number_of_players_max: result_value.number_of_players_max,
             wager: BigUint::<StaticApi>::to_u64(&result_value.wager).expect("e greu"),
+            token_id: String::from_utf8(
+                result_value
+                    .token_id
+                    .into_name()
+                    .to_boxed_bytes()
+                    .into_vec(),
+            )
+            .unwrap(),
             creator: result_value.creator.to_address().into(),
             status: result_value.status,
         }
     }
 
-    async fn game_id(&mut self) {
-        let game_settings = proxy::GameSettings {
-            time_limit: 0u64,
-            number_of_players_min: 0u64,
-            number_of_players_max: 0u64,
-            wager: BigUint::<StaticApi>::from(0u128),
-            creator: ManagedAddress::from_address(&Address::from_slice(
-                SECOND_USER_ADDR.as_bytes(),
-            )),
-            status: proxy::Status::Invalid,
-        };
+    async fn game_id_by_creator(&mut self) {
+        let creator = bech32::decode(SECOND_USER_ADDR);
+        let nonce = 1usize;
 
         let result_value = self
             .interactor
             .query()
             .to(self.state.current_address())
             .typed(proxy::MvxGameScProxy)
-            .game_id(proxy::GameSettings::from(game_settings))
+            .get_game_id_by_creator(creator, nonce)
             .returns(ReturnsResultUnmanaged)
             .prepare_async()
             .run()
@@ -549,6 +555,23 @@ impl ContractInteract {
         println!("Result: {result_value:?}");
     }
 
+    async fn game_info(&mut self) {
+        let game_id = 0u64;
+
+        let result_value = self
+            .interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::MvxGameScProxy)
+            .get_game_info(game_id)
+            .returns(ReturnsResult)
+            .prepare_async()
+            .run()
+            .await;
+
+        println!("Result: {result_value:?}");
+    }
+
     async fn games_per_user(&mut self) {
         let user = bech32::decode("");
 
@@ -569,12 +592,12 @@ impl ContractInteract {
     async fn send_reward(&mut self, sender: &Bech32Address, game_id: u64) {
         let winners = OptionalValue::Some(MultiValueVec::from(vec![
             (
-                ManagedAddress::from_address(&Address::from_slice(SECOND_USER_ADDR.as_bytes())),
-                0u64,
+                ManagedAddress::from_address(&bech32::decode(SECOND_USER_ADDR)),
+                5_000u64,
             ),
             (
-                ManagedAddress::from_address(&Address::from_slice(THIRD_USER_ADDR.as_bytes())),
-                0u64,
+                ManagedAddress::from_address(&bech32::decode(THIRD_USER_ADDR)),
+                5_000u64,
             ),
         ]));
 
@@ -602,12 +625,12 @@ impl ContractInteract {
     ) {
         let winners = OptionalValue::Some(MultiValueVec::from(vec![
             (
-                ManagedAddress::from_address(&Address::from_slice(SECOND_USER_ADDR.as_bytes())),
-                0u64,
+                ManagedAddress::from_address(&bech32::decode(SECOND_USER_ADDR)),
+                5_000u64,
             ),
             (
-                ManagedAddress::from_address(&Address::from_slice(THIRD_USER_ADDR.as_bytes())),
-                0u64,
+                ManagedAddress::from_address(&bech32::decode(THIRD_USER_ADDR)),
+                5_000u64,
             ),
         ]));
 
@@ -739,6 +762,84 @@ impl ContractInteract {
             .await;
     }
 
+    async fn set_game_limits(
+        &mut self,
+        sender: &Bech32Address,
+        waiting_time: (u64, u64),
+        number_of_players: (u64, u64),
+        wager: (u128, u128),
+    ) {
+        self.interactor
+            .tx()
+            .from(sender)
+            .to(self.state.current_address())
+            .gas(80_000_000u64)
+            .typed(proxy::MvxGameScProxy)
+            .set_waiting_time_limits(waiting_time.0, waiting_time.1)
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await;
+
+        self.interactor
+            .tx()
+            .from(sender)
+            .to(self.state.current_address())
+            .gas(80_000_000u64)
+            .typed(proxy::MvxGameScProxy)
+            .set_player_limits(number_of_players.0, number_of_players.1)
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await;
+
+        self.interactor
+            .tx()
+            .from(sender)
+            .to(self.state.current_address())
+            .gas(80_000_000u64)
+            .typed(proxy::MvxGameScProxy)
+            .set_token_min_wager(
+                EgldOrEsdtTokenIdentifier::esdt(TOKEN_ID),
+                BigUint::<StaticApi>::from(wager.0),
+            )
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await;
+
+        self.interactor
+            .tx()
+            .from(sender)
+            .to(self.state.current_address())
+            .gas(80_000_000u64)
+            .typed(proxy::MvxGameScProxy)
+            .set_token_max_wager(
+                EgldOrEsdtTokenIdentifier::esdt(TOKEN_ID),
+                BigUint::<StaticApi>::from(wager.1),
+            )
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await;
+    }
+
+    async fn game_limits(&mut self) -> proxy::GameLimits<StaticApi> {
+        let result_value = self
+            .interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::MvxGameScProxy)
+            .game_limits(EgldOrEsdtTokenIdentifier::esdt(TOKEN_ID))
+            .returns(ReturnsResultUnmanaged)
+            .prepare_async()
+            .run()
+            .await;
+
+        println!("Result: {result_value:?}");
+        result_value
+    }
+
     async fn set_admin(&mut self, user: &str) {
         let user = bech32::decode(user);
 
@@ -966,11 +1067,111 @@ async fn test_create_game() {
     assert_eq!(game_settings.number_of_players_min, 1u64);
     assert_eq!(game_settings.number_of_players_max, 1u64);
     assert_eq!(game_settings.wager, WAGE_AMOUNT);
+    assert_eq!(game_settings.token_id, TOKEN_ID);
     assert_eq!(
         game_settings.creator,
         Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string())
     );
-    assert_eq!(game_settings.status, proxy::Status::Invalid);
+    assert_eq!(game_settings.status, proxy::Status::Open);
+}
+
+#[tokio::test]
+async fn test_create_game_limits() {
+    let mut interact = ContractInteract::new().await;
+    interact
+        .deploy(
+            OptionalValue::Some(true),
+            OptionalValue::Some(BigUint::<StaticApi>::from(FEE_AMOUNT)),
+        )
+        .await;
+
+    interact
+        .set_game_limits(
+            &Bech32Address::from_bech32_string(OWNER_ADDR.to_string()),
+            (WAITING_TIME, 10 * WAITING_TIME),
+            (2u64, 4u64),
+            ((2 * WAGE_AMOUNT).into(), (10 * WAGE_AMOUNT).into()),
+        )
+        .await;
+
+    let limits = interact.game_limits().await;
+    assert_eq!(limits.waiting_time_min, WAITING_TIME);
+    assert_eq!(limits.waiting_time_max, 10 * WAITING_TIME);
+    assert_eq!(limits.number_of_players_min, 2u64);
+    assert_eq!(limits.number_of_players_max, 4u64);
+    assert_eq!(limits.wager_min, 2 * WAGE_AMOUNT);
+    assert_eq!(limits.wager_max, 10 * WAGE_AMOUNT);
+
+    let failures = [
+        (
+            WAITING_TIME - 1,
+            2u64,
+            4u64,
+            2 * WAGE_AMOUNT,
+            "waiting time too short",
+        ),
+        (
+            10 * WAITING_TIME + 1,
+            2u64,
+            4u64,
+            2 * WAGE_AMOUNT,
+            "waiting time too long",
+        ),
+        (
+            WAITING_TIME,
+            1u64,
+            4u64,
+            2 * WAGE_AMOUNT,
+            "min number of players too low",
+        ),
+        (
+            WAITING_TIME,
+            2u64,
+            5u64,
+            2 * WAGE_AMOUNT,
+            "max number of players too high",
+        ),
+        (WAITING_TIME, 2u64, 4u64, WAGE_AMOUNT, "wager too low"),
+        (
+            WAITING_TIME,
+            2u64,
+            4u64,
+            10 * WAGE_AMOUNT + 1,
+            "wager too high",
+        ),
+    ];
+    for (waiting_time, min, max, wager, error) in failures {
+        interact
+            .create_game_fail(
+                &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
+                TOKEN_ID,
+                0u64,
+                FEE_AMOUNT.into(),
+                waiting_time,
+                min,
+                max,
+                wager.into(),
+                ExpectError(4, error),
+            )
+            .await;
+    }
+
+    let game_id = interact
+        .create_game(
+            &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
+            TOKEN_ID,
+            0u64,
+            FEE_AMOUNT.into(),
+            WAITING_TIME,
+            2u64,
+            4u64,
+            (2 * WAGE_AMOUNT).into(),
+        )
+        .await;
+
+    let game_settings = interact.game_settings(game_id).await;
+    assert_eq!(game_settings.number_of_players_min, 2u64);
+    assert_eq!(game_settings.number_of_players_max, 4u64);
 }
 
 #[tokio::test]
@@ -1116,7 +1317,7 @@ async fn test_join_game() {
 
     let game_settings = interact.game_settings(game_id).await;
 
-    assert_eq!(game_settings.status, proxy::Status::Valid);
+    assert_eq!(game_settings.status, proxy::Status::InProgress);
 
     interact
         .join_game_fail(
@@ -1143,11 +1344,12 @@ async fn test_claim_back_wager() {
 
     interact.disable_sc().await;
 
+    //claims are still available during maintenance
     interact
         .claim_back_wager_fail(
             &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
             0u64,
-            ExpectError(4, "maintenance"),
+            ExpectError(4, "no settings for game id"),
         )
         .await;
 
@@ -1206,10 +1408,7 @@ async fn test_claim_back_wager() {
         .claim_back_wager_fail(
             &Bech32Address::from_bech32_string(THIRD_USER_ADDR.to_string()),
             game_id,
-            ExpectError(
-                4,
-                "can manually claim back wager only if the game is invalid",
-            ),
+            ExpectError(4, "can manually claim back wager only if the game is open"),
         )
         .await;
 
@@ -1315,7 +1514,7 @@ async fn test_owner_small_funcs() {
         .set_token_id_fail(
             &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
             ANOTHER_TOKEN_ID,
-            ExpectError(4, "Endpoint can only be called by owner"),
+            ExpectError(4, "caller does not have the required role"),
         )
         .await;
 
@@ -1335,7 +1534,7 @@ async fn test_owner_small_funcs() {
         .set_token_id_fail(
             &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
             ANOTHER_TOKEN_ID,
-            ExpectError(4, "Endpoint can only be called by owner"),
+            ExpectError(4, "caller does not have the required role"),
         )
         .await;
 
@@ -1354,7 +1553,7 @@ async fn test_owner_small_funcs() {
         .set_game_start_fee_fail(
             &Bech32Address::from_bech32_string(SECOND_USER_ADDR.to_string()),
             (FEE_AMOUNT * 2).into(),
-            ExpectError(4, "Endpoint can only be called by owner"),
+            ExpectError(4, "caller does not have the required role"),
         )
         .await;
 }
@@ -1452,7 +1651,7 @@ async fn test_full_func() {
         .claim_back_wager_fail(
             &Bech32Address::from_bech32_string(THIRD_USER_ADDR.to_string()),
             game_id,
-            ExpectError(4, "no settings for game id"),
+            ExpectError(4, "can manually claim back wager only if the game is open"),
         )
         .await;
 
@@ -0,0 +1,260 @@
+use crate::types::{GameSettings, Operation, Role, Status};
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    //GAME
+    #[event("createGame")]
+    fn create_game_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] creator: &ManagedAddress,
+        game_settings: &GameSettings<Self::Api>,
+    );
+
+    #[event("joinGame")]
+    fn join_game_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] player: &ManagedAddress,
+        wager: &BigUint,
+    );
+
+    #[event("claimBackWager")]
+    fn claim_back_wager_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] player: &ManagedAddress,
+        wager: &BigUint,
+    );
+
+    #[event("leaveGame")]
+    fn leave_game_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] player: &ManagedAddress,
+        refund: &BigUint,
+    );
+
+    #[event("cancelGame")]
+    fn cancel_game_event(&self, #[indexed] game_id: u64, #[indexed] caller: &ManagedAddress);
+
+    #[event("emergencyWithdraw")]
+    fn emergency_withdraw_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] caller: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("claimRewards")]
+    fn claim_rewards_event(
+        &self,
+        #[indexed] user: &ManagedAddress,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("proposeSettlement")]
+    fn propose_settlement_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] proposer: &ManagedAddress,
+        expires_at: u64,
+    );
+
+    #[event("approveSettlement")]
+    fn approve_settlement_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] approver: &ManagedAddress,
+    );
+
+    #[event("executeSettlement")]
+    fn execute_settlement_event(&self, #[indexed] game_id: u64, #[indexed] caller: &ManagedAddress);
+
+    #[event("expireSettlement")]
+    fn expire_settlement_event(&self, #[indexed] game_id: u64, #[indexed] caller: &ManagedAddress);
+
+    #[event("submitResult")]
+    fn submit_result_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] submitted_by: &ManagedAddress,
+        challenge_ends_at: u64,
+    );
+
+    #[event("disputeResult")]
+    fn dispute_result_event(&self, #[indexed] game_id: u64, #[indexed] player: &ManagedAddress);
+
+    #[event("resolveDispute")]
+    fn resolve_dispute_event(&self, #[indexed] game_id: u64, upheld: bool);
+
+    #[event("finalizeResult")]
+    fn finalize_result_event(&self, #[indexed] game_id: u64, #[indexed] caller: &ManagedAddress);
+
+    #[event("submitSignedResult")]
+    fn submit_signed_result_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] caller: &ManagedAddress,
+    );
+
+    //emitted on every stored status change (e.g. Ready when the game becomes valid)
+    //a ready game whose waiting time is over is InProgress without a stored change
+    #[event("gameStatus")]
+    fn game_status_event(&self, #[indexed] game_id: u64, #[indexed] status: &Status);
+
+    #[event("rewardPaid")]
+    fn reward_paid_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] winner: &ManagedAddress,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("collectRake")]
+    fn collect_rake_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("collectFees")]
+    fn collect_fees_event(
+        &self,
+        #[indexed] game_id: u64,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    //OWNER
+    #[event("enableSC")]
+    fn enable_sc_event(&self);
+
+    #[event("disableSC")]
+    fn disable_sc_event(&self);
+
+    #[event("setEmergencyMode")]
+    fn set_emergency_mode_event(&self, #[indexed] emergency_mode: bool);
+
+    #[event("setEmergencyTimeout")]
+    fn set_emergency_timeout_event(&self, emergency_timeout: u64);
+
+    #[event("pauseOperation")]
+    fn pause_operation_event(&self, #[indexed] operation: &Operation);
+
+    #[event("unpauseOperation")]
+    fn unpause_operation_event(&self, #[indexed] operation: &Operation);
+
+    #[event("setTokenId")]
+    fn set_token_id_event(&self, #[indexed] token_id: &EgldOrEsdtTokenIdentifier);
+
+    #[event("setGameStartFee")]
+    fn set_game_start_fee_event(&self, amount: &BigUint);
+
+    #[event("addAcceptedToken")]
+    fn add_accepted_token_event(&self, #[indexed] token_id: &EgldOrEsdtTokenIdentifier);
+
+    #[event("removeAcceptedToken")]
+    fn remove_accepted_token_event(&self, #[indexed] token_id: &EgldOrEsdtTokenIdentifier);
+
+    #[event("setTokenMinWager")]
+    fn set_token_min_wager_event(
+        &self,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("setTokenMaxWager")]
+    fn set_token_max_wager_event(
+        &self,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("setWaitingTimeLimits")]
+    fn set_waiting_time_limits_event(&self, #[indexed] min: u64, #[indexed] max: u64);
+
+    #[event("setPlayerLimits")]
+    fn set_player_limits_event(&self, #[indexed] min: u64, #[indexed] max: u64);
+
+    #[event("setTokenStartFee")]
+    fn set_token_start_fee_event(
+        &self,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("setRake")]
+    fn set_rake_event(&self, rake: u64);
+
+    #[event("setLeavePenalty")]
+    fn set_leave_penalty_event(&self, leave_penalty: u64);
+
+    #[event("setForfeitStartFeeOnCancel")]
+    fn set_forfeit_start_fee_on_cancel_event(&self, forfeit: bool);
+
+    #[event("migrateLegacyGames")]
+    fn migrate_legacy_games_event(&self, #[indexed] from_game_id: u64, #[indexed] to_game_id: u64);
+
+    #[event("setScheduleBounds")]
+    fn set_schedule_bounds_event(&self, #[indexed] min_lead: u64, #[indexed] max_lead: u64);
+
+    #[event("setSettlementTimeout")]
+    fn set_settlement_timeout_event(&self, settlement_timeout: u64);
+
+    #[event("setSettlementBatchSize")]
+    fn set_settlement_batch_size_event(&self, batch_size: usize);
+
+    #[event("setPullPayouts")]
+    fn set_pull_payouts_event(&self, pull_payouts: bool);
+
+    #[event("setQuorumThreshold")]
+    fn set_quorum_threshold_event(
+        &self,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("setRequiredApprovals")]
+    fn set_required_approvals_event(&self, required_approvals: usize);
+
+    #[event("setProposalDuration")]
+    fn set_proposal_duration_event(&self, duration: u64);
+
+    #[event("setChallengePeriod")]
+    fn set_challenge_period_event(&self, challenge_period: u64);
+
+    #[event("setResultSigner")]
+    fn set_result_signer_event(&self, public_key: &ManagedBuffer);
+
+    #[event("withdrawRake")]
+    fn withdraw_rake_event(
+        &self,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("withdrawFees")]
+    fn withdraw_fees_event(
+        &self,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("setAdmin")]
+    fn set_admin_event(&self, #[indexed] user: &ManagedAddress);
+
+    #[event("removeAdmin")]
+    fn remove_admin_event(&self, #[indexed] user: &ManagedAddress);
+
+    #[event("grantRole")]
+    fn grant_role_event(&self, #[indexed] user: &ManagedAddress, #[indexed] role: &Role);
+
+    #[event("revokeRole")]
+    fn revoke_role_event(&self, #[indexed] user: &ManagedAddress, #[indexed] role: &Role);
+}
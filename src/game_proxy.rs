@@ -63,6 +63,25 @@ where
     }
 }
 
+#[rustfmt::skip]
+impl<Env, From, To, Gas> MvxGameScProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
 #[rustfmt::skip]
 impl<Env, From, To, Gas> MvxGameScProxyMethods<Env, From, To, Gas>
 where
@@ -74,238 +93,1372 @@ where
 {
     pub fn create_game<
         Arg0: ProxyArg<u64>,
-        Arg1: ProxyArg<u64>,
-        Arg2: ProxyArg<u64>,
-        Arg3: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<BigUint<Env::Api>>,
+        Arg4: ProxyArg<OptionalValue<u64>>,
+        Arg5: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        waiting_time: Arg0,
+        number_of_players_min: Arg1,
+        number_of_players_max: Arg2,
+        wager: Arg3,
+        join_deadline_opt: Arg4,
+        start_time_opt: Arg5,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("createGame")
+            .argument(&waiting_time)
+            .argument(&number_of_players_min)
+            .argument(&number_of_players_max)
+            .argument(&wager)
+            .argument(&join_deadline_opt)
+            .argument(&start_time_opt)
+            .original_result()
+    }
+
+    pub fn join_game<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("joinGame")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn leave_game<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("leaveGame")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn cancel_game<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelGame")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn claim_back_wager<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimBackWager")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn emergency_withdraw<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("emergencyWithdraw")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn dispute_result<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("disputeResult")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn finalize_result<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("finalizeResult")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn claim_rewards(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimRewards")
+            .original_result()
+    }
+
+    pub fn game_status<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Status> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGameStatus")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn token_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, EgldOrEsdtTokenIdentifier<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenId")
+            .original_result()
+    }
+
+    pub fn game_start_fee(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGameStartFee")
+            .original_result()
+    }
+
+    pub fn accepted_tokens(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, EgldOrEsdtTokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAcceptedTokens")
+            .original_result()
+    }
+
+    pub fn token_min_wager<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenMinWager")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn token_max_wager<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenMaxWager")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn token_start_fee<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTokenStartFee")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn rake(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRake")
+            .original_result()
+    }
+
+    pub fn leave_penalty(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLeavePenalty")
+            .original_result()
+    }
+
+    pub fn forfeit_start_fee_on_cancel(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getForfeitStartFeeOnCancel")
+            .original_result()
+    }
+
+    pub fn treasury<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTreasury")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn collected_fees<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectedFees")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn min_waiting_time(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinWaitingTime")
+            .original_result()
+    }
+
+    pub fn max_waiting_time(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxWaitingTime")
+            .original_result()
+    }
+
+    pub fn min_players(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinPlayers")
+            .original_result()
+    }
+
+    pub fn max_players(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxPlayers")
+            .original_result()
+    }
+
+    pub fn min_schedule_lead(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMinScheduleLead")
+            .original_result()
+    }
+
+    pub fn max_schedule_lead(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxScheduleLead")
+            .original_result()
+    }
+
+    pub fn settlement_timeout(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlementTimeout")
+            .original_result()
+    }
+
+    pub fn settlement_batch_size(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlementBatchSize")
+            .original_result()
+    }
+
+    pub fn pull_payouts(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPullPayouts")
+            .original_result()
+    }
+
+    pub fn quorum_threshold<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getQuorumThreshold")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn required_approvals(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRequiredApprovals")
+            .original_result()
+    }
+
+    pub fn proposal_duration(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProposalDuration")
+            .original_result()
+    }
+
+    pub fn challenge_period(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getChallengePeriod")
+            .original_result()
+    }
+
+    pub fn result_signer(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getResultSigner")
+            .original_result()
+    }
+
+    pub fn storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    pub fn enabled(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEnabled")
+            .original_result()
+    }
+
+    pub fn emergency_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isEmergencyMode")
+            .original_result()
+    }
+
+    pub fn emergency_timeout(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEmergencyTimeout")
+            .original_result()
+    }
+
+    pub fn paused<
+        Arg0: ProxyArg<Operation>,
+    >(
+        self,
+        operation: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isOperationPaused")
+            .argument(&operation)
+            .original_result()
+    }
+
+    pub fn is_user_admin<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isUserAdmin")
+            .argument(&user)
+            .original_result()
+    }
+
+    pub fn last_game_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastGameId")
+            .original_result()
+    }
+
+    pub fn game_settings<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GameSettings<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGameSettings")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn games_by_creator<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        creator: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGamesByCreator")
+            .argument(&creator)
+            .original_result()
+    }
+
+    pub fn legacy_last_game_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLegacyLastGameId")
+            .original_result()
+    }
+
+    pub fn legacy_migrated_game_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLegacyMigratedGameId")
+            .original_result()
+    }
+
+    pub fn open_games(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOpenGameIds")
+            .original_result()
+    }
+
+    pub fn settlement<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Settlement<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlement")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn pending_result<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, PendingResult<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPendingResult")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn settlement_proposal<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SettlementProposal<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSettlementProposal")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn signed_result_nonce<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSignedResultNonce")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn game_result<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GameResult<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGameResult")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn game_escrow<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGameEscrow")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn players<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getPlayers")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn claimable<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<EgldOrEsdtTokenIdentifier<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getClaimable")
+            .argument(&user)
+            .original_result()
+    }
+
+    pub fn games_per_user<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGamesPerUser")
+            .argument(&user)
+            .original_result()
+    }
+
+    pub fn send_reward<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<MultiValueEncoded<Env::Api, (ManagedAddress<Env::Api>, u64)>>>,
+    >(
+        self,
+        game_id: Arg0,
+        winners: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("sendReward")
+            .argument(&game_id)
+            .argument(&winners)
+            .original_result()
+    }
+
+    pub fn approve_settlement<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("approveSettlement")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn execute_settlement<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executeSettlement")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn expire_settlement<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("expireSettlement")
+            .argument(&game_id)
+            .original_result()
+    }
+
+    pub fn submit_signed_result<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<MultiValueEncoded<Env::Api, (ManagedAddress<Env::Api>, u64)>>>,
+    >(
+        self,
+        game_id: Arg0,
+        signature: Arg1,
+        winners: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("submitSignedResult")
+            .argument(&game_id)
+            .argument(&signature)
+            .argument(&winners)
+            .original_result()
+    }
+
+    pub fn resolve_dispute<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        game_id: Arg0,
+        uphold: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("resolveDispute")
+            .argument(&game_id)
+            .argument(&uphold)
+            .original_result()
+    }
+
+    pub fn continue_settlement<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        game_id: Arg0,
+        batch_size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("continueSettlement")
+            .argument(&game_id)
+            .argument(&batch_size)
+            .original_result()
+    }
+
+    pub fn migrate_legacy_games<
+        Arg0: ProxyArg<usize>,
+        Arg1: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        batch_size: Arg0,
+        paid_game_ids: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateLegacyGames")
+            .argument(&batch_size)
+            .argument(&paid_game_ids)
+            .original_result()
+    }
+
+    pub fn enable_sc(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("enableSC")
+            .original_result()
+    }
+
+    pub fn disable_sc(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("disableSC")
+            .original_result()
+    }
+
+    pub fn pause_operation<
+        Arg0: ProxyArg<Operation>,
+    >(
+        self,
+        operation: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pauseOperation")
+            .argument(&operation)
+            .original_result()
+    }
+
+    pub fn unpause_operation<
+        Arg0: ProxyArg<Operation>,
+    >(
+        self,
+        operation: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unpauseOperation")
+            .argument(&operation)
+            .original_result()
+    }
+
+    pub fn set_token_id<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTokenId")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn add_accepted_token<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addAcceptedToken")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn remove_accepted_token<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeAcceptedToken")
+            .argument(&token_id)
+            .original_result()
+    }
+
+    pub fn set_token_min_wager<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTokenMinWager")
+            .argument(&token_id)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_token_max_wager<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTokenMaxWager")
+            .argument(&token_id)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_waiting_time_limits<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        min: Arg0,
+        max: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWaitingTimeLimits")
+            .argument(&min)
+            .argument(&max)
+            .original_result()
+    }
+
+    pub fn set_player_limits<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        min: Arg0,
+        max: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setPlayerLimits")
+            .argument(&min)
+            .argument(&max)
+            .original_result()
+    }
+
+    pub fn set_token_start_fee<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTokenStartFee")
+            .argument(&token_id)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_game_start_fee<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        amount: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setGameStartFee")
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_rake<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        rake: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRake")
+            .argument(&rake)
+            .original_result()
+    }
+
+    pub fn set_leave_penalty<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        leave_penalty: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setLeavePenalty")
+            .argument(&leave_penalty)
+            .original_result()
+    }
+
+    pub fn set_forfeit_start_fee_on_cancel<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        forfeit: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setForfeitStartFeeOnCancel")
+            .argument(&forfeit)
+            .original_result()
+    }
+
+    pub fn set_schedule_bounds<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        min_lead: Arg0,
+        max_lead: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setScheduleBounds")
+            .argument(&min_lead)
+            .argument(&max_lead)
+            .original_result()
+    }
+
+    pub fn set_settlement_timeout<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        settlement_timeout: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSettlementTimeout")
+            .argument(&settlement_timeout)
+            .original_result()
+    }
+
+    pub fn set_settlement_batch_size<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        batch_size: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setSettlementBatchSize")
+            .argument(&batch_size)
+            .original_result()
+    }
+
+    pub fn set_pull_payouts<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        pull_payouts: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setPullPayouts")
+            .argument(&pull_payouts)
+            .original_result()
+    }
+
+    pub fn set_quorum_threshold<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        token_id: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setQuorumThreshold")
+            .argument(&token_id)
+            .argument(&amount)
+            .original_result()
+    }
+
+    pub fn set_required_approvals<
+        Arg0: ProxyArg<usize>,
+    >(
+        self,
+        required_approvals: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRequiredApprovals")
+            .argument(&required_approvals)
+            .original_result()
+    }
+
+    pub fn set_proposal_duration<
+        Arg0: ProxyArg<u64>,
     >(
         self,
-        waiting_time: Arg0,
-        number_of_players_min: Arg1,
-        number_of_players_max: Arg2,
-        wager: Arg3,
-    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        duration: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
-            .raw_call("createGame")
-            .argument(&waiting_time)
-            .argument(&number_of_players_min)
-            .argument(&number_of_players_max)
-            .argument(&wager)
+            .payment(NotPayable)
+            .raw_call("setProposalDuration")
+            .argument(&duration)
             .original_result()
     }
 
-    pub fn join_game<
+    pub fn set_emergency_mode<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        emergency_mode: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setEmergencyMode")
+            .argument(&emergency_mode)
+            .original_result()
+    }
+
+    pub fn set_emergency_timeout<
         Arg0: ProxyArg<u64>,
     >(
         self,
-        game_id: Arg0,
-    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        emergency_timeout: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
-            .raw_call("joinGame")
-            .argument(&game_id)
+            .payment(NotPayable)
+            .raw_call("setEmergencyTimeout")
+            .argument(&emergency_timeout)
             .original_result()
     }
 
-    pub fn claim_back_wager<
+    pub fn set_challenge_period<
         Arg0: ProxyArg<u64>,
     >(
         self,
-        game_id: Arg0,
+        challenge_period: Arg0,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("claimBackWager")
-            .argument(&game_id)
+            .raw_call("setChallengePeriod")
+            .argument(&challenge_period)
             .original_result()
     }
 
-    pub fn token_id(
+    pub fn set_result_signer<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, EgldOrEsdtTokenIdentifier<Env::Api>> {
+        public_key: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getTokenId")
+            .raw_call("setResultSigner")
+            .argument(&public_key)
             .original_result()
     }
 
-    pub fn game_start_fee(
+    pub fn withdraw_rake<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        token_id: Arg0,
+        amount_opt: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getGameStartFee")
+            .raw_call("withdrawRake")
+            .argument(&token_id)
+            .argument(&amount_opt)
             .original_result()
     }
 
-    pub fn enabled(
+    pub fn withdraw_fees<
+        Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        token_id: Arg0,
+        amount_opt: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getEnabled")
+            .raw_call("withdrawFees")
+            .argument(&token_id)
+            .argument(&amount_opt)
             .original_result()
     }
 
-    pub fn is_user_admin<
+    pub fn set_admin<
         Arg0: ProxyArg<ManagedAddress<Env::Api>>,
     >(
         self,
         user: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("isUserAdmin")
+            .raw_call("setAdmin")
             .argument(&user)
             .original_result()
     }
 
-    pub fn last_game_id(
+    pub fn remove_admin<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getLastGameId")
+            .raw_call("removeAdmin")
+            .argument(&user)
             .original_result()
     }
 
-    pub fn game_settings<
-        Arg0: ProxyArg<u64>,
+    pub fn grant_role<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<Role>,
     >(
         self,
-        game_id: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GameSettings<Env::Api>> {
+        user: Arg0,
+        role: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getGameSettings")
-            .argument(&game_id)
+            .raw_call("grantRole")
+            .argument(&user)
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<Role>,
+    >(
+        self,
+        user: Arg0,
+        role: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&user)
+            .argument(&role)
             .original_result()
     }
 
-    pub fn game_id<
-        Arg0: ProxyArg<GameSettings<Env::Api>>,
+    pub fn get_game_id_by_creator<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
     >(
         self,
-        game_settings: Arg0,
+        creator: Arg0,
+        nonce: Arg1,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getGameIdBySettings")
-            .argument(&game_settings)
+            .raw_call("getGameIdByCreator")
+            .argument(&creator)
+            .argument(&nonce)
             .original_result()
     }
 
-    pub fn players<
+    pub fn get_game_info<
         Arg0: ProxyArg<u64>,
     >(
         self,
         game_id: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GameInfo<Env::Api>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getPlayers")
+            .raw_call("getGameInfo")
             .argument(&game_id)
             .original_result()
     }
 
-    pub fn games_per_user<
-        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    pub fn get_games_info<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
     >(
         self,
-        user: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        game_ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, GameInfo<Env::Api>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getGamesPerUser")
-            .argument(&user)
+            .raw_call("getGamesInfo")
+            .argument(&game_ids)
             .original_result()
     }
 
-    pub fn send_reward<
-        Arg0: ProxyArg<u64>,
-        Arg1: ProxyArg<OptionalValue<MultiValueEncoded<Env::Api, (ManagedAddress<Env::Api>, u64)>>>,
+    pub fn get_open_games<
+        Arg0: ProxyArg<usize>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<GameFilter<Env::Api>>,
     >(
         self,
-        game_id: Arg0,
-        winners: Arg1,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        offset: Arg0,
+        size: Arg1,
+        filter: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<u64, GameSettings<Env::Api>>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("sendReward")
-            .argument(&game_id)
-            .argument(&winners)
+            .raw_call("getOpenGames")
+            .argument(&offset)
+            .argument(&size)
+            .argument(&filter)
             .original_result()
     }
 
-    pub fn enable_sc(
+    pub fn get_games<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<GameFilter<Env::Api>>,
+    >(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        from_game_id: Arg0,
+        size: Arg1,
+        filter: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<u64, GameSettings<Env::Api>>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("enableSC")
+            .raw_call("getGames")
+            .argument(&from_game_id)
+            .argument(&size)
+            .argument(&filter)
             .original_result()
     }
 
-    pub fn disable_sc(
+    pub fn get_pause_matrix(
         self,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<Operation, bool>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("disableSC")
+            .raw_call("getPauseMatrix")
             .original_result()
     }
 
-    pub fn set_token_id<
+    pub fn game_limits<
         Arg0: ProxyArg<EgldOrEsdtTokenIdentifier<Env::Api>>,
     >(
         self,
         token_id: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GameLimits<Env::Api>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("setTokenId")
+            .raw_call("getGameLimits")
             .argument(&token_id)
             .original_result()
     }
 
-    pub fn set_game_start_fee<
-        Arg0: ProxyArg<BigUint<Env::Api>>,
+    pub fn get_roles<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
     >(
         self,
-        amount: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        user: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Role>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("setGameStartFee")
-            .argument(&amount)
+            .raw_call("getRoles")
+            .argument(&user)
             .original_result()
     }
 
-    pub fn set_admin<
-        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
-    >(
+    pub fn get_pending_proposals(
         self,
-        user: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, SettlementProposal<Env::Api>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("setAdmin")
-            .argument(&user)
+            .raw_call("getPendingProposals")
             .original_result()
     }
 
-    pub fn remove_admin<
-        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    pub fn get_proposal_approvers<
+        Arg0: ProxyArg<u64>,
     >(
         self,
-        user: Arg0,
-    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        game_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("removeAdmin")
-            .argument(&user)
+            .raw_call("getProposalApprovers")
+            .argument(&game_id)
             .original_result()
     }
 }
 
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub enum Status {
+    Open,
+    Ready,
+    InProgress,
+    Settled,
+    Cancelled,
+    Refunded,
+    Settling,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub enum Operation {
+    Creation,
+    Joining,
+    Settlement,
+    Claims,
+}
+
 #[type_abi]
 #[derive(
     TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
@@ -318,15 +1471,147 @@ where
     pub number_of_players_min: u64,
     pub number_of_players_max: u64,
     pub wager: BigUint<Api>,
+    pub token_id: EgldOrEsdtTokenIdentifier<Api>,
+    pub rake: u64,
+    pub leave_penalty: u64,
+    pub forfeit_start_fee: bool,
+    pub settlement_deadline: u64,
+    pub join_deadline: u64,
+    pub start_time: u64,
+    pub creator: ManagedAddress<Api>,
+    pub status: Status,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct Settlement<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub status: Status,
+    pub refund_wagers: bool,
+    pub pull_payouts: bool,
+    pub player_count: u64,
+    pub processed: u64,
+    pub rake: BigUint<Api>,
+    pub rewards: ManagedVec<Api, Reward<Api>>,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub struct Reward<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub winner: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct PendingResult<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub submitted_by: ManagedAddress<Api>,
+    pub winners: ManagedVec<Api, WinnerShare<Api>>,
+    pub challenge_ends_at: u64,
+    pub disputed_by: Option<ManagedAddress<Api>>,
+    pub dispute_resolved: bool,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub struct WinnerShare<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub winner: ManagedAddress<Api>,
+    pub percentage: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct SettlementProposal<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub game_id: u64,
+    pub proposer: ManagedAddress<Api>,
+    pub winners: ManagedVec<Api, WinnerShare<Api>>,
+    pub approvers: ManagedVec<Api, ManagedAddress<Api>>,
+    pub expires_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameResult<Api>
+where
+    Api: ManagedTypeApi,
+{
     pub creator: ManagedAddress<Api>,
+    pub token_id: EgldOrEsdtTokenIdentifier<Api>,
+    pub wager: BigUint<Api>,
+    pub player_count: u64,
     pub status: Status,
+    pub rake: BigUint<Api>,
+    pub rewards: ManagedVec<Api, Reward<Api>>,
+    pub settled_at: u64,
 }
 
 #[type_abi]
 #[derive(
     TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
 )]
-pub enum Status {
-    Valid,
-    Invalid,
+pub enum Role {
+    ConfigManager,
+    Settler,
+    Pauser,
+    Treasurer,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameInfo<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub game_id: u64,
+    pub game_settings: GameSettings<Api>,
+    pub player_count: u64,
+    pub players: ManagedVec<Api, ManagedAddress<Api>>,
+    pub pool: BigUint<Api>,
+    pub time_remaining: u64,
+    pub status: Status,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameFilter<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub status: Option<Status>,
+    pub wager_min: Option<BigUint<Api>>,
+    pub wager_max: Option<BigUint<Api>>,
+    pub creator: Option<ManagedAddress<Api>>,
+    pub token_id: Option<EgldOrEsdtTokenIdentifier<Api>>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameLimits<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub waiting_time_min: u64,
+    pub waiting_time_max: u64,
+    pub number_of_players_min: u64,
+    pub number_of_players_max: u64,
+    pub wager_min: BigUint<Api>,
+    pub wager_max: BigUint<Api>,
 }
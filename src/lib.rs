@@ -2,14 +2,24 @@
 
 use multiversx_sc::imports::*;
 
+use types::{GameSettings, Operation, Status};
+
+pub mod events;
 pub mod game_proxy;
 pub mod owner;
 pub mod private;
 pub mod storage;
 pub mod types;
+pub mod views;
 
 #[multiversx_sc::contract]
-pub trait MvxGameSc: storage::StorageModule + owner::OwnerModule + private::PrivateModule {
+pub trait MvxGameSc:
+    storage::StorageModule
+    + owner::OwnerModule
+    + private::PrivateModule
+    + events::EventsModule
+    + views::ViewsModule
+{
     #[allow_multiple_var_args]
     #[init]
     fn init(
@@ -31,11 +41,26 @@ pub trait MvxGameSc: storage::StorageModule + owner::OwnerModule + private::Priv
         }
 
         match token_id_opt {
-            OptionalValue::Some(val) => self.token_id().set(val),
+            OptionalValue::Some(val) => {
+                self.accepted_tokens().insert(val.clone());
+                self.token_id().set(val);
+            }
             OptionalValue::None => require!(!self.token_id().is_empty(), "fee token id not set"),
         }
+
+        self.storage_version().set(owner::STORAGE_VERSION);
     }
 
+    #[upgrade]
+    fn upgrade(&self) {
+        //the legacy deployments did not set a storage version
+        if self.storage_version().is_empty() {
+            self.start_legacy_migration();
+        }
+    }
+
+    //the waiting time can be replaced by an absolute join deadline (waiting time 0)
+    #[allow_multiple_var_args]
     #[payable("*")]
     #[endpoint(createGame)]
     fn create_game(
@@ -44,48 +69,267 @@ pub trait MvxGameSc: storage::StorageModule + owner::OwnerModule + private::Priv
         number_of_players_min: u64,
         number_of_players_max: u64,
         wager: BigUint,
+        join_deadline_opt: OptionalValue<u64>,
+        start_time_opt: OptionalValue<u64>,
     ) -> u64 {
-        self.require_enabled();
+        self.require_not_paused(Operation::Creation);
 
-        let (token_id, amount) = self.call_value().single_fungible_esdt();
-        self.validate_create_game_payment(&token_id, &amount, &wager, waiting_time);
+        let join_deadline = join_deadline_opt.into_option().unwrap_or_default();
+        let start_time = start_time_opt.into_option().unwrap_or_default();
+        let time_limit = self.get_time_limit(waiting_time, join_deadline, start_time);
 
-        let (min, max) = self.get_min_max(number_of_players_min, number_of_players_max);
+        let (token_id, amount) = self.call_value().egld_or_single_fungible_esdt();
+        let now = self.blockchain().get_block_timestamp();
+        let (min, max) = self.validate_create_game_payment(
+            &token_id,
+            &amount,
+            &wager,
+            time_limit - now,
+            number_of_players_min,
+            number_of_players_max,
+        );
 
         let caller = self.blockchain().get_caller();
 
-        self.create_new_game(caller, waiting_time, min, max, wager)
+        let game_settings = GameSettings {
+            time_limit,
+            number_of_players_min: min,
+            number_of_players_max: max,
+            wager,
+            token_id,
+            rake: self.rake().get(),
+            leave_penalty: self.leave_penalty().get(),
+            forfeit_start_fee: self.forfeit_start_fee_on_cancel().get(),
+            settlement_deadline: 0,
+            join_deadline,
+            start_time,
+            creator: caller,
+            status: Status::Open,
+        };
+        let game_id = self.create_new_game(game_settings);
+        self.deposit_to_escrow(game_id, &amount);
+
+        game_id
     }
 
     #[payable("*")]
     #[endpoint(joinGame)]
     fn join_game(&self, game_id: u64) {
-        self.require_enabled();
+        self.require_not_paused(Operation::Joining);
 
-        let (token_id, amount) = self.call_value().single_fungible_esdt();
+        let (token_id, amount) = self.call_value().egld_or_single_fungible_esdt();
         let now = self.blockchain().get_block_timestamp();
         let caller = self.blockchain().get_caller();
 
         let game_settings = self.validate_join_game(&caller, now, &token_id, &amount, game_id);
 
-        self.add_player(caller, game_id);
+        self.add_player(caller.clone(), game_id);
+        self.deposit_to_escrow(game_id, &amount);
+        self.join_game_event(game_id, &caller, &amount);
 
         self.refresh_game_status(game_id, game_settings);
     }
 
-    //manually claim back wager if the game is invalid
+    //leave the game before it starts, the leave penalty set at creation is kept as a fee
+    #[endpoint(leaveGame)]
+    fn leave_game(&self, game_id: u64) {
+        self.require_not_paused(Operation::Claims);
+
+        let caller = self.blockchain().get_caller();
+        let game_settings = self.validate_leave_game(&caller, game_id);
+
+        let refund = self.collect_leave_penalty(game_id, &game_settings);
+        self.send_from_escrow(game_id, &caller, &game_settings.token_id, &refund);
+        self.remove_player(caller.clone(), game_id);
+
+        self.leave_game_event(game_id, &caller, &refund);
+
+        self.refresh_game_status(game_id, game_settings);
+    }
+
+    //the creator or an admin can cancel a game before it starts, all the wagers are returned
+    //in batches, like a refund, see continueSettlement
+    #[endpoint(cancelGame)]
+    fn cancel_game(&self, game_id: u64) {
+        self.require_not_paused(Operation::Claims);
+
+        let caller = self.blockchain().get_caller();
+        self.validate_cancel_game(&caller, game_id);
+
+        self.cancel_game_event(game_id, &caller);
+        self.start_cancellation(game_id);
+    }
+
+    //manually claim back wager if the game did not get enough players in time
     #[endpoint(claimBackWager)]
     fn claim_back_wager(&self, game_id: u64) {
-        self.require_enabled();
+        self.require_not_paused(Operation::Claims);
+
+        let caller = self.blockchain().get_caller();
+        let game_settings = self.validate_claim_wager(&caller, game_id);
+
+        self.send_from_escrow(
+            game_id,
+            &caller,
+            &game_settings.token_id,
+            &game_settings.wager,
+        );
+        self.remove_player(caller.clone(), game_id);
+        //the waiting time is over, the game can't be joined anymore
+        self.open_games().swap_remove(&game_id);
+
+        self.claim_back_wager_event(game_id, &caller, &game_settings.wager);
+
+        //a game past its settlement deadline can't be settled anymore, the last player closes it
+        if self.is_settlement_deadline_over(&game_settings) && self.players(game_id).is_empty() {
+            self.pending_result(game_id).clear();
+            self.clear_settlement_proposal(game_id);
+            self.send_back_start_fee(game_id, &game_settings);
+            self.set_game_status(game_id, Status::Refunded);
+        }
+    }
+
+    //in emergency mode the players of an unsettled game get back their wager and the creator
+    //the start fee, even if the claims are paused
+    #[endpoint(emergencyWithdraw)]
+    fn emergency_withdraw(&self, game_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let game_settings = self.validate_emergency_withdraw(game_id);
+
+        if !self.emergency_mode().get() {
+            require!(
+                self.is_emergency_timeout_over(&game_settings),
+                "emergency mode is not active"
+            );
+
+            self.emergency_mode().set(true);
+            self.set_emergency_mode_event(true);
+        }
+
+        //the outcome of a settlement in progress is already fixed, its next batch is paid out
+        //as continueSettlement would, even if the settlements or the claims are paused
+        if game_settings.status == Status::Settling {
+            self.process_settlement(game_id, self.get_settlement_batch_size());
+            return;
+        }
+
+        let is_player = self.games_per_user(&caller).contains(&game_id);
+        let wager_refund = if is_player {
+            game_settings.wager.clone()
+        } else {
+            BigUint::zero()
+        };
+
+        //the wagers of the players left stay in the escrow
+        let start_fee = if caller == game_settings.creator {
+            let wagers = &BigUint::from(self.players(game_id).len() as u64) * &game_settings.wager;
+            self.game_escrow(game_id).get() - wagers
+        } else {
+            BigUint::zero()
+        };
+
+        let amount = wager_refund + start_fee;
+        require!(amount > 0u64, "nothing to withdraw");
+
+        if is_player {
+            self.remove_player(caller.clone(), game_id);
+        }
+        self.send_from_escrow(game_id, &caller, &game_settings.token_id, &amount);
+        self.emergency_withdraw_event(game_id, &caller, &amount);
+
+        //the result can't be paid anymore
+        self.pending_result(game_id).clear();
+        self.clear_settlement_proposal(game_id);
+
+        if self.players(game_id).is_empty() && self.game_escrow(game_id).get() == 0u64 {
+            self.set_game_status(game_id, Status::Refunded);
+        }
+    }
 
+    //a player of the game can dispute its result during the challenge period, freezing the payout
+    //it can't be paused, like emergencyWithdraw, or a paused dispute would let the result be finalized
+    #[endpoint(disputeResult)]
+    fn dispute_result(&self, game_id: u64) {
         let caller = self.blockchain().get_caller();
-        let wager = self.validate_claim_wager(&caller, game_id);
-
-        let token_id = self.token_id().get();
-        self.tx()
-            .to(&caller)
-            .egld_or_single_esdt(&token_id, 0, &wager)
-            .transfer();
-        self.remove_player(caller, game_id);
+        let mut pending_result = self.validate_pending_result(game_id);
+
+        require!(
+            self.games_per_user(&caller).contains(&game_id),
+            "caller has not joined the game"
+        );
+        require!(
+            pending_result.disputed_by.is_none(),
+            "result already disputed"
+        );
+
+        let now = self.blockchain().get_block_timestamp();
+        require!(
+            now <= pending_result.challenge_ends_at,
+            "challenge period is over"
+        );
+
+        pending_result.disputed_by = Some(caller.clone());
+        self.pending_result(game_id).set(pending_result);
+        self.dispute_result_event(game_id, &caller);
+    }
+
+    //anyone can start the payout once the challenge period is over and no dispute is open
+    #[endpoint(finalizeResult)]
+    fn finalize_result(&self, game_id: u64) {
+        self.require_not_paused(Operation::Settlement);
+
+        let pending_result = self.validate_pending_result(game_id);
+        let now = self.blockchain().get_block_timestamp();
+
+        require!(
+            now > pending_result.challenge_ends_at,
+            "challenge period is not over yet"
+        );
+        require!(
+            pending_result.disputed_by.is_none() || pending_result.dispute_resolved,
+            "result is disputed"
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.pending_result(game_id).clear();
+        self.finalize_result_event(game_id, &caller);
+
+        self.start_settlement(game_id, self.get_winners_arg(&pending_result.winners));
+    }
+
+    //withdraw the rewards and refunds kept by the contract, in every token
+    #[endpoint(claimRewards)]
+    fn claim_rewards(&self) {
+        self.require_not_paused(Operation::Claims);
+
+        let caller = self.blockchain().get_caller();
+        let mut claimable = self.claimable(&caller);
+        require!(!claimable.is_empty(), "nothing to claim");
+
+        for (token_id, amount) in claimable.iter() {
+            self.tx()
+                .to(&caller)
+                .egld_or_single_esdt(&token_id, 0, &amount)
+                .transfer();
+
+            self.claim_rewards_event(&caller, &token_id, &amount);
+        }
+
+        claimable.clear();
+    }
+
+    #[view(getGameStatus)]
+    fn game_status(&self, game_id: u64) -> Status {
+        if !self.game_result(game_id).is_empty() {
+            return self.game_result(game_id).get().status;
+        }
+
+        require!(
+            !self.game_settings(game_id).is_empty(),
+            "no settings for game id"
+        );
+
+        let game_settings = self.game_settings(game_id).get();
+        self.get_game_status(&game_settings)
     }
 }
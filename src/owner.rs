@@ -1,11 +1,17 @@
-use crate::types::Status;
+use crate::types::{Operation, Role, Status};
 
 use multiversx_sc::imports::*;
 
-const DENOM: u64 = 10_000u64;
+pub const DENOM: u64 = 10_000u64;
+pub const DEFAULT_SETTLEMENT_BATCH_SIZE: usize = 100;
+pub const DEFAULT_PROPOSAL_DURATION: u64 = 86_400u64; //one day
+pub const ED25519_KEY_LENGTH: usize = 32;
+pub const STORAGE_VERSION: u32 = 1;
 
 #[multiversx_sc::module]
-pub trait OwnerModule: crate::private::PrivateModule + crate::storage::StorageModule {
+pub trait OwnerModule:
+    crate::private::PrivateModule + crate::storage::StorageModule + crate::events::EventsModule
+{
     //u64 is percentage * 100
     //function called by the owner/admins when the winners have been decided
     #[endpoint(sendReward)]
@@ -14,83 +20,516 @@ pub trait OwnerModule: crate::private::PrivateModule + crate::storage::StorageMo
         game_id: u64,
         winners: OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>>,
     ) {
-        self.require_enabled();
+        self.require_not_paused(Operation::Settlement);
 
         let caller = self.blockchain().get_caller();
         self.admins().require_whitelisted(&caller);
 
-        let game_settings = self.validate_send_reward(game_id);
-        let token_id = self.token_id().get();
+        require!(
+            self.settlement_proposal(game_id).is_empty(),
+            "settlement proposal already pending"
+        );
 
-        match game_settings.status {
-            Status::Invalid => {
-                self.send_back_wager(game_id, &game_settings.wager, &token_id);
+        //paying the winners of a high value game needs the approval of other admins
+        if let OptionalValue::Some(val) = &winners {
+            let game_settings = self.validate_send_reward(game_id);
+            if game_settings.status != Status::Open && self.requires_quorum(game_id, &game_settings)
+            {
+                self.propose_settlement(game_id, &caller, val.clone());
+                return;
+            }
+        }
+
+        self.submit_result(game_id, &caller, winners);
+    }
 
-                let game_creation_fee = self.game_start_fee().get();
-                self.tx()
-                    .to(game_settings.creator)
-                    .egld_or_single_esdt(&token_id, 0, &game_creation_fee)
-                    .transfer();
+    #[endpoint(approveSettlement)]
+    fn approve_settlement(&self, game_id: u64) {
+        self.require_not_paused(Operation::Settlement);
 
-                self.game_settings(game_id).clear();
-            }
-            Status::Valid => {
-                match winners {
-                    OptionalValue::Some(val) => {
-                        let len = self.players(game_id).len();
-                        let total_wager = &BigUint::from(len) * &game_settings.wager;
-
-                        for (winner, percentage) in val.into_iter() {
-                            let reward_per_winner =
-                                &BigUint::from(percentage) * &total_wager / &BigUint::from(DENOM);
-                            self.tx()
-                                .to(winner)
-                                .egld_or_single_esdt(&token_id, 0, &reward_per_winner)
-                                .transfer();
-                        }
-                    }
-                    //tie/draw
-                    OptionalValue::None => {
-                        self.send_back_wager(game_id, &game_settings.wager, &token_id);
-                    }
-                }
-            }
+        let caller = self.blockchain().get_caller();
+        self.admins().require_whitelisted(&caller);
+
+        let mut proposal = self.validate_settlement_proposal(game_id);
+        require!(
+            !proposal.approvers.contains(&caller),
+            "settlement already approved"
+        );
+
+        proposal.approvers.push(caller.clone());
+        self.settlement_proposal(game_id).set(proposal);
+        self.approve_settlement_event(game_id, &caller);
+    }
+
+    //settles the game once enough admins approved the proposal
+    #[endpoint(executeSettlement)]
+    fn execute_settlement(&self, game_id: u64) {
+        self.require_not_paused(Operation::Settlement);
+
+        let caller = self.blockchain().get_caller();
+        self.admins().require_whitelisted(&caller);
+
+        let proposal = self.validate_settlement_proposal(game_id);
+        require!(
+            self.count_approvals(&proposal) >= self.required_approvals().get(),
+            "not enough approvals"
+        );
+
+        self.execute_settlement_event(game_id, &caller);
+        self.submit_result(game_id, &caller, self.get_winners_arg(&proposal.winners));
+    }
+
+    //removes a proposal that was not executed in time, so a new one can be made
+    #[endpoint(expireSettlement)]
+    fn expire_settlement(&self, game_id: u64) {
+        self.require_not_paused(Operation::Settlement);
+
+        let caller = self.blockchain().get_caller();
+        self.admins().require_whitelisted(&caller);
+
+        require!(
+            !self.settlement_proposal(game_id).is_empty(),
+            "no settlement proposal for game id"
+        );
+
+        let now = self.blockchain().get_block_timestamp();
+        require!(
+            now > self.settlement_proposal(game_id).get().expires_at,
+            "settlement proposal not expired yet"
+        );
+
+        self.clear_settlement_proposal(game_id);
+        self.expire_settlement_event(game_id, &caller);
+    }
+
+    //same as sendReward, but the result is signed off-chain by the result signer
+    //and can be submitted by anyone, each signature once, see getSignedResultNonce
+    #[endpoint(submitSignedResult)]
+    fn submit_signed_result(
+        &self,
+        game_id: u64,
+        signature: ManagedBuffer,
+        winners: OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>>,
+    ) {
+        self.require_not_paused(Operation::Settlement);
+
+        require!(!self.result_signer().is_empty(), "result signer not set");
+
+        //the nonce changes with every submission, so a rejected result can't be replayed
+        let nonce = self.signed_result_nonce(game_id).get();
+        let message = self.get_result_message(game_id, nonce, &winners);
+        self.crypto()
+            .verify_ed25519(&self.result_signer().get(), &message, &signature);
+
+        let caller = self.blockchain().get_caller();
+        self.signed_result_nonce(game_id).set(nonce + 1);
+        self.submit_signed_result_event(game_id, &caller);
+
+        self.submit_result(game_id, &caller, winners);
+    }
+
+    //the owner either upholds the disputed result, which is paid after the challenge period,
+    //or rejects it, so a new result can be submitted
+    #[only_owner]
+    #[endpoint(resolveDispute)]
+    fn resolve_dispute(&self, game_id: u64, uphold: bool) {
+        let mut pending_result = self.validate_pending_result(game_id);
+        require!(
+            pending_result.disputed_by.is_some() && !pending_result.dispute_resolved,
+            "result is not disputed"
+        );
+
+        if uphold {
+            pending_result.dispute_resolved = true;
+            self.pending_result(game_id).set(pending_result);
+        } else {
+            self.pending_result(game_id).clear();
         }
+
+        self.resolve_dispute_event(game_id, uphold);
     }
 
+    //resumes a settlement that did not process every player in one batch
+    //anyone can call it, like finalizeResult, the payouts were fixed when the settlement started
+    #[endpoint(continueSettlement)]
+    fn continue_settlement(&self, game_id: u64, batch_size: usize) {
+        require!(
+            !self.settlement(game_id).is_empty(),
+            "no settlement in progress"
+        );
+
+        //refunds and cancellations stay available during maintenance, like the other claims
+        let operation = if self.settlement(game_id).get().status == Status::Settled {
+            Operation::Settlement
+        } else {
+            Operation::Claims
+        };
+        self.require_not_paused(operation);
+
+        require!(batch_size > 0, "batch size can't be 0");
+
+        self.process_settlement(game_id, batch_size);
+    }
+
+    //migrates the next batch of legacy games, the legacy contract did not record the payouts,
+    //so the ids of the games it already paid have to be given to archive them
     #[only_owner]
+    #[endpoint(migrateLegacyGames)]
+    fn migrate_legacy_games(&self, batch_size: usize, paid_game_ids: MultiValueEncoded<u64>) {
+        require!(
+            self.is_legacy_migration_pending(),
+            "no legacy games to migrate"
+        );
+        require!(batch_size > 0, "batch size can't be 0");
+
+        let from_game_id = self.legacy_migrated_game_id().get() + 1;
+        let to_game_id = core::cmp::min(
+            from_game_id + batch_size as u64 - 1,
+            self.legacy_last_game_id().get(),
+        );
+
+        let paid_game_ids = paid_game_ids.to_vec();
+        for game_id in paid_game_ids.iter() {
+            require!(
+                (from_game_id..=to_game_id).contains(&game_id),
+                "paid game not in batch"
+            );
+        }
+
+        for game_id in from_game_id..=to_game_id {
+            self.migrate_legacy_game(game_id, paid_game_ids.contains(&game_id));
+        }
+
+        self.legacy_migrated_game_id().set(to_game_id);
+        self.migrate_legacy_games_event(from_game_id, to_game_id);
+    }
+
     #[endpoint(enableSC)]
     fn enable_sc(&self) {
-        self.enabled().set(true)
+        self.require_role(Role::Pauser);
+
+        self.enabled().set(true);
+        self.enable_sc_event();
     }
 
-    #[only_owner]
     #[endpoint(disableSC)]
     fn disable_sc(&self) {
-        self.enabled().clear()
+        self.require_role(Role::Pauser);
+
+        self.enabled().clear();
+        self.disable_sc_event();
+    }
+
+    //pausing the claims also blocks the refunds, which are available during maintenance otherwise
+    #[endpoint(pauseOperation)]
+    fn pause_operation(&self, operation: Operation) {
+        self.require_role(Role::Pauser);
+
+        self.pause_operation_event(&operation);
+        self.paused(&operation).set(true);
+    }
+
+    #[endpoint(unpauseOperation)]
+    fn unpause_operation(&self, operation: Operation) {
+        self.require_role(Role::Pauser);
+
+        self.unpause_operation_event(&operation);
+        self.paused(&operation).clear();
     }
 
-    #[only_owner]
     #[endpoint(setTokenId)]
     fn set_token_id(&self, token_id: EgldOrEsdtTokenIdentifier) {
-        self.token_id().set(token_id)
+        self.require_role(Role::ConfigManager);
+
+        //replaces the main token, games already created keep using their own token
+        if !self.token_id().is_empty() {
+            self.accepted_tokens().swap_remove(&self.token_id().get());
+        }
+
+        self.accepted_tokens().insert(token_id.clone());
+        self.set_token_id_event(&token_id);
+        self.token_id().set(token_id);
+    }
+
+    #[endpoint(addAcceptedToken)]
+    fn add_accepted_token(&self, token_id: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::ConfigManager);
+
+        require!(token_id.is_valid(), "invalid token id");
+
+        self.add_accepted_token_event(&token_id);
+        self.accepted_tokens().insert(token_id);
+    }
+
+    #[endpoint(removeAcceptedToken)]
+    fn remove_accepted_token(&self, token_id: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::ConfigManager);
+
+        self.accepted_tokens().swap_remove(&token_id);
+        self.remove_accepted_token_event(&token_id);
+    }
+
+    #[endpoint(setTokenMinWager)]
+    fn set_token_min_wager(&self, token_id: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_token_min_wager_event(&token_id, &amount);
+        self.token_min_wager(&token_id).set(amount);
+    }
+
+    //0 removes the max
+    #[endpoint(setTokenMaxWager)]
+    fn set_token_max_wager(&self, token_id: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_token_max_wager_event(&token_id, &amount);
+        self.token_max_wager(&token_id).set(amount);
+    }
+
+    //bounds of the waiting time of the new games, 0 removes the max
+    #[endpoint(setWaitingTimeLimits)]
+    fn set_waiting_time_limits(&self, min: u64, max: u64) {
+        self.require_role(Role::ConfigManager);
+        require!(max == 0 || min <= max, "invalid limits");
+
+        self.set_waiting_time_limits_event(min, max);
+        self.min_waiting_time().set(min);
+        self.max_waiting_time().set(max);
+    }
+
+    //bounds of the min and max number of players of the new games, 0 removes the max
+    #[endpoint(setPlayerLimits)]
+    fn set_player_limits(&self, min: u64, max: u64) {
+        self.require_role(Role::ConfigManager);
+        require!(max == 0 || min <= max, "invalid limits");
+
+        self.set_player_limits_event(min, max);
+        self.min_players().set(min);
+        self.max_players().set(max);
+    }
+
+    #[endpoint(setTokenStartFee)]
+    fn set_token_start_fee(&self, token_id: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_token_start_fee_event(&token_id, &amount);
+        self.token_start_fee(&token_id).set(amount);
     }
 
-    #[only_owner]
     #[endpoint(setGameStartFee)]
     fn set_game_start_fee(&self, amount: BigUint) {
-        self.game_start_fee().set(amount)
+        self.require_role(Role::ConfigManager);
+
+        self.set_game_start_fee_event(&amount);
+        self.game_start_fee().set(amount);
+    }
+
+    #[endpoint(setRake)]
+    fn set_rake(&self, rake: u64) {
+        self.require_role(Role::ConfigManager);
+
+        require!(rake < DENOM, "rake must be lower than 100%");
+
+        self.set_rake_event(rake);
+        self.rake().set(rake);
+    }
+
+    #[endpoint(setLeavePenalty)]
+    fn set_leave_penalty(&self, leave_penalty: u64) {
+        self.require_role(Role::ConfigManager);
+
+        require!(
+            leave_penalty < DENOM,
+            "leave penalty must be lower than 100%"
+        );
+
+        self.set_leave_penalty_event(leave_penalty);
+        self.leave_penalty().set(leave_penalty);
+    }
+
+    #[endpoint(setForfeitStartFeeOnCancel)]
+    fn set_forfeit_start_fee_on_cancel(&self, forfeit: bool) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_forfeit_start_fee_on_cancel_event(forfeit);
+        self.forfeit_start_fee_on_cancel().set(forfeit);
+    }
+
+    //bounds of the join deadline and start time chosen by the creators, 0 removes the max
+    #[endpoint(setScheduleBounds)]
+    fn set_schedule_bounds(&self, min_lead: u64, max_lead: u64) {
+        self.require_role(Role::ConfigManager);
+        require!(
+            max_lead == 0 || min_lead <= max_lead,
+            "invalid schedule bounds"
+        );
+
+        self.set_schedule_bounds_event(min_lead, max_lead);
+        self.min_schedule_lead().set(min_lead);
+        self.max_schedule_lead().set(max_lead);
+    }
+
+    //applies to the games created afterwards, 0 removes the deadline
+    #[endpoint(setSettlementTimeout)]
+    fn set_settlement_timeout(&self, settlement_timeout: u64) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_settlement_timeout_event(settlement_timeout);
+        self.settlement_timeout().set(settlement_timeout);
+    }
+
+    #[endpoint(setSettlementBatchSize)]
+    fn set_settlement_batch_size(&self, batch_size: usize) {
+        self.require_role(Role::ConfigManager);
+
+        require!(batch_size > 0, "batch size can't be 0");
+
+        self.set_settlement_batch_size_event(batch_size);
+        self.settlement_batch_size().set(batch_size);
+    }
+
+    #[endpoint(setPullPayouts)]
+    fn set_pull_payouts(&self, pull_payouts: bool) {
+        self.require_role(Role::ConfigManager);
+
+        self.set_pull_payouts_event(pull_payouts);
+        self.pull_payouts().set(pull_payouts);
+    }
+
+    #[only_owner]
+    #[endpoint(setQuorumThreshold)]
+    fn set_quorum_threshold(&self, token_id: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.set_quorum_threshold_event(&token_id, &amount);
+        self.quorum_threshold(&token_id).set(amount);
+    }
+
+    //1 disables the quorum
+    #[only_owner]
+    #[endpoint(setRequiredApprovals)]
+    fn set_required_approvals(&self, required_approvals: usize) {
+        require!(required_approvals > 0, "required approvals can't be 0");
+
+        self.set_required_approvals_event(required_approvals);
+        self.required_approvals().set(required_approvals);
+    }
+
+    #[only_owner]
+    #[endpoint(setProposalDuration)]
+    fn set_proposal_duration(&self, duration: u64) {
+        require!(duration > 0, "proposal duration can't be 0");
+
+        self.set_proposal_duration_event(duration);
+        self.proposal_duration().set(duration);
+    }
+
+    #[only_owner]
+    #[endpoint(setEmergencyMode)]
+    fn set_emergency_mode(&self, emergency_mode: bool) {
+        self.set_emergency_mode_event(emergency_mode);
+        self.emergency_mode().set(emergency_mode);
+    }
+
+    //0 disables the automatic emergency mode
+    #[only_owner]
+    #[endpoint(setEmergencyTimeout)]
+    fn set_emergency_timeout(&self, emergency_timeout: u64) {
+        self.set_emergency_timeout_event(emergency_timeout);
+        self.emergency_timeout().set(emergency_timeout);
+    }
+
+    //0 disables the challenge period, results are paid right away
+    #[only_owner]
+    #[endpoint(setChallengePeriod)]
+    fn set_challenge_period(&self, challenge_period: u64) {
+        self.set_challenge_period_event(challenge_period);
+        self.challenge_period().set(challenge_period);
+    }
+
+    #[only_owner]
+    #[endpoint(setResultSigner)]
+    fn set_result_signer(&self, public_key: ManagedBuffer) {
+        require!(
+            public_key.len() == ED25519_KEY_LENGTH,
+            "invalid result signer public key"
+        );
+
+        self.set_result_signer_event(&public_key);
+        self.result_signer().set(public_key);
+    }
+
+    #[endpoint(withdrawRake)]
+    fn withdraw_rake(
+        &self,
+        token_id: EgldOrEsdtTokenIdentifier,
+        amount_opt: OptionalValue<BigUint>,
+    ) {
+        self.require_role(Role::Treasurer);
+
+        //a treasurer can trigger the withdrawal, the funds always go to the owner
+        let owner = self.blockchain().get_owner_address();
+        let amount =
+            self.withdraw_owner_funds(&owner, self.treasury(&token_id), &token_id, amount_opt);
+
+        self.withdraw_rake_event(&owner, &token_id, &amount);
+    }
+
+    #[endpoint(withdrawFees)]
+    fn withdraw_fees(
+        &self,
+        token_id: EgldOrEsdtTokenIdentifier,
+        amount_opt: OptionalValue<BigUint>,
+    ) {
+        self.require_role(Role::Treasurer);
+
+        let owner = self.blockchain().get_owner_address();
+        let amount = self.withdraw_owner_funds(
+            &owner,
+            self.collected_fees(&token_id),
+            &token_id,
+            amount_opt,
+        );
+
+        self.withdraw_fees_event(&owner, &token_id, &amount);
     }
 
     #[only_owner]
     #[endpoint(setAdmin)]
     fn set_admin(&self, user: ManagedAddress) {
-        self.admins().add(&user)
+        self.admins().add(&user);
+        self.set_admin_event(&user);
     }
 
     #[only_owner]
     #[endpoint(removeAdmin)]
     fn remove_admin(&self, user: ManagedAddress) {
-        self.admins().remove(&user)
+        self.admins().remove(&user);
+        self.remove_admin_event(&user);
+    }
+
+    //granting the settler role adds the user to the admins
+    #[only_owner]
+    #[endpoint(grantRole)]
+    fn grant_role(&self, user: ManagedAddress, role: Role) {
+        match role {
+            Role::Settler => self.admins().add(&user),
+            _ => {
+                self.roles(&user).insert(role.clone());
+            }
+        }
+
+        self.grant_role_event(&user, &role);
+    }
+
+    #[only_owner]
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, user: ManagedAddress, role: Role) {
+        match role {
+            Role::Settler => self.admins().remove(&user),
+            _ => {
+                self.roles(&user).swap_remove(&role);
+            }
+        }
+
+        self.revoke_role_event(&user, &role);
     }
 }
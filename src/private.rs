@@ -1,38 +1,358 @@
-use crate::types::{GameSettings, Status};
+use crate::{
+    owner::{DEFAULT_PROPOSAL_DURATION, DEFAULT_SETTLEMENT_BATCH_SIZE, DENOM, STORAGE_VERSION},
+    types::{
+        GameLimits, GameResult, GameSettings, LegacyStatus, Operation, PendingResult, Reward, Role,
+        Settlement, SettlementProposal, Status, WinnerShare,
+    },
+};
 
 use multiversx_sc::imports::*;
 
 #[multiversx_sc::module]
-pub trait PrivateModule: crate::storage::StorageModule {
+pub trait PrivateModule: crate::storage::StorageModule + crate::events::EventsModule {
     //game
-    fn create_new_game(
-        &self,
-        caller: ManagedAddress,
-        waiting_time: u64,
-        min: u64,
-        max: u64,
-        wager: BigUint,
-    ) -> u64 {
+    fn create_new_game(&self, mut game_settings: GameSettings<Self::Api>) -> u64 {
         let new_id = self.get_new_game_id();
         self.last_game_id().set(new_id);
-        let now = self.blockchain().get_block_timestamp();
 
-        let time_limit = now + waiting_time;
-        let game_settings = GameSettings {
-            time_limit,
-            number_of_players_min: min,
-            number_of_players_max: max,
-            wager,
-            creator: caller,
-            status: Status::Invalid,
-        };
+        let settlement_timeout = self.settlement_timeout().get();
+        if settlement_timeout > 0 {
+            game_settings.settlement_deadline =
+                self.get_game_start(&game_settings) + settlement_timeout;
+        }
 
-        self.game_id(&game_settings).set(new_id);
+        self.games_by_creator(&game_settings.creator).push(&new_id);
+        self.open_games().insert(new_id);
+        self.create_game_event(new_id, &game_settings.creator, &game_settings);
         self.game_settings(new_id).set(game_settings);
 
         new_id
     }
 
+    //the games created before the upgrade are migrated in batches by migrateLegacyGames,
+    //no game can be created or played until then
+    fn start_legacy_migration(&self) {
+        self.legacy_last_game_id().set(self.last_game_id().get());
+        self.accepted_tokens().insert(self.token_id().get());
+        self.storage_version().set(STORAGE_VERSION);
+    }
+
+    fn is_legacy_migration_pending(&self) -> bool {
+        self.legacy_migrated_game_id().get() < self.legacy_last_game_id().get()
+    }
+
+    //the legacy games used the token of the contract, and their escrow holds the wagers and the start fee
+    fn migrate_legacy_game(&self, game_id: u64, paid: bool) {
+        //the legacy contract removed the settings of the refunded games
+        if self.legacy_game_settings(game_id).is_empty() {
+            require!(!paid, "paid game has no settings");
+            return;
+        }
+
+        let legacy_settings = self.legacy_game_settings(game_id).get();
+        let creator = legacy_settings.creator.clone();
+        self.games_by_creator(&creator).push(&game_id);
+
+        //the legacy index was keyed by the settings at creation
+        let mut legacy_key = legacy_settings.clone();
+        legacy_key.status = LegacyStatus::Invalid;
+        self.legacy_game_id(&legacy_key).clear();
+
+        let token_id = self.token_id().get();
+        let player_count = self.players(game_id).len() as u64;
+        if paid {
+            require!(
+                legacy_settings.status == LegacyStatus::Valid,
+                "paid game was not valid"
+            );
+
+            for player in self.players(game_id).iter() {
+                self.games_per_user(&player).swap_remove(&game_id);
+            }
+            self.players(game_id).clear();
+            self.game_settings(game_id).clear();
+
+            //archived like a settled game, without the rewards
+            self.game_result(game_id).set(GameResult {
+                creator,
+                token_id,
+                wager: legacy_settings.wager,
+                player_count,
+                status: Status::Settled,
+                rake: BigUint::zero(),
+                rewards: ManagedVec::new(),
+                settled_at: self.blockchain().get_block_timestamp(),
+            });
+            return;
+        }
+
+        let status = if legacy_settings.number_of_players_max <= player_count {
+            Status::InProgress
+        } else if legacy_settings.number_of_players_min <= player_count {
+            Status::Ready
+        } else {
+            Status::Open
+        };
+        if status != Status::InProgress {
+            self.open_games().insert(game_id);
+        }
+
+        let wagers = &BigUint::from(player_count) * &legacy_settings.wager;
+        self.game_escrow(game_id)
+            .set(wagers + self.game_start_fee().get());
+
+        self.game_settings(game_id).set(GameSettings {
+            time_limit: legacy_settings.time_limit,
+            number_of_players_min: legacy_settings.number_of_players_min,
+            number_of_players_max: legacy_settings.number_of_players_max,
+            wager: legacy_settings.wager,
+            token_id,
+            rake: 0,
+            leave_penalty: 0,
+            forfeit_start_fee: false,
+            settlement_deadline: 0,
+            join_deadline: 0,
+            start_time: 0,
+            creator,
+            status,
+        });
+    }
+
+    //with a challenge period the result is kept until the period is over, so players can dispute it
+    fn submit_result(
+        &self,
+        game_id: u64,
+        submitted_by: &ManagedAddress,
+        winners: OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>>,
+    ) {
+        let game_settings = self.validate_send_reward(game_id);
+        let challenge_period = self.challenge_period().get();
+
+        //not enough players, there is no result to dispute
+        if game_settings.status == Status::Open || challenge_period == 0 {
+            self.start_settlement(game_id, winners);
+            return;
+        }
+
+        let winner_shares = match winners {
+            OptionalValue::Some(val) => self.get_winner_shares(game_id, val),
+            OptionalValue::None => ManagedVec::new(),
+        };
+        let challenge_ends_at = self.blockchain().get_block_timestamp() + challenge_period;
+
+        self.clear_settlement_proposal(game_id);
+        self.pending_result(game_id).set(PendingResult {
+            submitted_by: submitted_by.clone(),
+            winners: winner_shares,
+            challenge_ends_at,
+            disputed_by: None,
+            dispute_resolved: false,
+        });
+        self.submit_result_event(game_id, submitted_by, challenge_ends_at);
+    }
+
+    //computes the rewards, or marks the game for refund, then pays the first batch of players
+    fn start_settlement(
+        &self,
+        game_id: u64,
+        winners: OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>>,
+    ) {
+        let game_settings = self.validate_send_reward(game_id);
+        let player_count = self.players(game_id).len() as u64;
+        self.clear_settlement_proposal(game_id);
+
+        let mut settlement = Settlement {
+            status: Status::Settled,
+            refund_wagers: true,
+            pull_payouts: self.pull_payouts().get(),
+            player_count,
+            processed: 0,
+            rake: BigUint::zero(),
+            rewards: ManagedVec::new(),
+        };
+
+        //min number of players not reached before the waiting time was over
+        if game_settings.status == Status::Open {
+            settlement.status = Status::Refunded;
+        } else if let OptionalValue::Some(val) = winners {
+            let total_wager = &BigUint::from(player_count) * &game_settings.wager;
+            let prize_pool = self.collect_rake(game_id, &game_settings, total_wager.clone());
+
+            settlement.refund_wagers = false;
+            settlement.rake = total_wager - &prize_pool;
+            let winner_shares = self.get_winner_shares(game_id, val);
+            settlement.rewards = self.get_rewards(&prize_pool, &winner_shares);
+            for reward in settlement.rewards.iter() {
+                self.pending_reward(game_id, &reward.winner)
+                    .set(&reward.amount);
+            }
+        }
+        //otherwise tie/draw, no rake is taken and the wagers are returned
+
+        self.settlement(game_id).set(settlement);
+        self.process_settlement(game_id, self.get_settlement_batch_size());
+    }
+
+    fn start_cancellation(&self, game_id: u64) {
+        let settlement = Settlement {
+            status: Status::Cancelled,
+            refund_wagers: true,
+            pull_payouts: self.pull_payouts().get(),
+            player_count: self.players(game_id).len() as u64,
+            processed: 0,
+            rake: BigUint::zero(),
+            rewards: ManagedVec::new(),
+        };
+
+        self.settlement(game_id).set(settlement);
+        self.process_settlement(game_id, self.get_settlement_batch_size());
+    }
+
+    //the proposer is the first approver, the payout waits for the other admins
+    fn propose_settlement(
+        &self,
+        game_id: u64,
+        proposer: &ManagedAddress,
+        winners: MultiValueEncoded<(ManagedAddress, u64)>,
+    ) {
+        let expires_at = self.blockchain().get_block_timestamp() + self.get_proposal_duration();
+
+        let mut approvers = ManagedVec::new();
+        approvers.push(proposer.clone());
+
+        let proposal = SettlementProposal {
+            game_id,
+            proposer: proposer.clone(),
+            winners: self.get_winner_shares(game_id, winners),
+            approvers,
+            expires_at,
+        };
+
+        self.pending_proposals().insert(game_id);
+        self.settlement_proposal(game_id).set(proposal);
+        self.propose_settlement_event(game_id, proposer, expires_at);
+    }
+
+    fn clear_settlement_proposal(&self, game_id: u64) {
+        self.settlement_proposal(game_id).clear();
+        self.pending_proposals().swap_remove(&game_id);
+    }
+
+    //pays the players in batches, the last player processed closes the game
+    fn process_settlement(&self, game_id: u64, batch_size: usize) {
+        let mut settlement = self.settlement(game_id).get();
+        let game_settings = self.game_settings(game_id).get();
+        let token_id = &game_settings.token_id;
+        let mut players = self.players(game_id);
+
+        for _ in 0..batch_size {
+            if players.is_empty() {
+                break;
+            }
+
+            let player = players.get_by_index(players.len());
+            players.swap_remove(&player);
+            self.games_per_user(&player).swap_remove(&game_id);
+
+            if settlement.refund_wagers {
+                self.pay_out(
+                    game_id,
+                    &player,
+                    token_id,
+                    &game_settings.wager,
+                    settlement.pull_payouts,
+                );
+            } else {
+                let reward = self.pending_reward(game_id, &player).take();
+                if reward > 0u64 {
+                    self.pay_out(game_id, &player, token_id, &reward, settlement.pull_payouts);
+                    self.reward_paid_event(game_id, &player, token_id, &reward);
+                }
+            }
+
+            settlement.processed += 1;
+        }
+
+        if !players.is_empty() {
+            if game_settings.status != Status::Settling {
+                self.set_game_status(game_id, Status::Settling);
+            }
+            self.settlement(game_id).set(settlement);
+            return;
+        }
+
+        self.settlement(game_id).clear();
+
+        //only the start fee paid by the creator is left in the escrow, it is returned if the
+        //game did not take place, unless the cancel policy forfeits it
+        let keep_start_fee = match settlement.status {
+            Status::Settled => true,
+            Status::Cancelled => game_settings.forfeit_start_fee,
+            _ => false,
+        };
+
+        if keep_start_fee {
+            self.collect_start_fee(game_id, token_id);
+        } else {
+            let game_creation_fee = self.game_escrow(game_id).get();
+            self.pay_out(
+                game_id,
+                &game_settings.creator,
+                token_id,
+                &game_creation_fee,
+                settlement.pull_payouts,
+            );
+        }
+
+        self.set_game_status(game_id, settlement.status.clone());
+        self.archive_game(game_id, &game_settings, settlement);
+    }
+
+    //closed games only keep a compact result, the settings and escrow are removed
+    fn archive_game(
+        &self,
+        game_id: u64,
+        game_settings: &GameSettings<Self::Api>,
+        settlement: Settlement<Self::Api>,
+    ) {
+        let game_result = GameResult {
+            creator: game_settings.creator.clone(),
+            token_id: game_settings.token_id.clone(),
+            wager: game_settings.wager.clone(),
+            player_count: settlement.player_count,
+            status: settlement.status,
+            rake: settlement.rake,
+            rewards: settlement.rewards,
+            settled_at: self.blockchain().get_block_timestamp(),
+        };
+
+        self.game_settings(game_id).clear();
+        self.game_escrow(game_id).clear();
+        self.game_result(game_id).set(game_result);
+    }
+
+    //only the creator, token, wager and player count of an archived game are kept
+    fn get_archived_settings(
+        &self,
+        game_result: &GameResult<Self::Api>,
+    ) -> GameSettings<Self::Api> {
+        GameSettings {
+            time_limit: 0,
+            number_of_players_min: game_result.player_count,
+            number_of_players_max: game_result.player_count,
+            wager: game_result.wager.clone(),
+            token_id: game_result.token_id.clone(),
+            rake: 0,
+            leave_penalty: 0,
+            forfeit_start_fee: false,
+            settlement_deadline: 0,
+            join_deadline: 0,
+            start_time: 0,
+            creator: game_result.creator.clone(),
+            status: game_result.status.clone(),
+        }
+    }
+
     fn add_player(&self, caller: ManagedAddress, game_id: u64) {
         self.games_per_user(&caller).insert(game_id);
         self.players(game_id).insert(caller);
@@ -45,53 +365,354 @@ pub trait PrivateModule: crate::storage::StorageModule {
 
     fn refresh_game_status(&self, game_id: u64, game_settings: GameSettings<Self::Api>) {
         let len = self.players(game_id).len() as u64;
-        if game_settings.number_of_players_min <= len {
-            self.game_settings(game_id)
-                .update(|val| val.status = Status::Valid);
+        let status = if game_settings.number_of_players_max <= len {
+            Status::InProgress
+        } else if game_settings.number_of_players_min <= len {
+            Status::Ready
+        } else {
+            Status::Open
+        };
+
+        if status != game_settings.status {
+            self.set_game_status(game_id, status);
+        }
+    }
+
+    fn set_game_status(&self, game_id: u64, status: Status) {
+        if status != Status::Open && status != Status::Ready {
+            self.open_games().swap_remove(&game_id);
+        }
+
+        self.game_status_event(game_id, &status);
+        self.game_settings(game_id)
+            .update(|val| val.status = status);
+    }
+
+    //a ready game starts by itself once the waiting time is over
+    fn get_game_status(&self, game_settings: &GameSettings<Self::Api>) -> Status {
+        let now = self.blockchain().get_block_timestamp();
+        match game_settings.status {
+            Status::Ready if now > game_settings.time_limit => Status::InProgress,
+            _ => game_settings.status.clone(),
+        }
+    }
+
+    //only the start fee paid by the creator is left in the escrow
+    fn send_back_start_fee(&self, game_id: u64, game_settings: &GameSettings<Self::Api>) {
+        let game_creation_fee = self.game_escrow(game_id).get();
+        self.send_from_escrow(
+            game_id,
+            &game_settings.creator,
+            &game_settings.token_id,
+            &game_creation_fee,
+        );
+    }
+
+    //pull payouts are moved from the game escrow to the user's claimable balance
+    fn pay_out(
+        &self,
+        game_id: u64,
+        to: &ManagedAddress,
+        token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        pull_payouts: bool,
+    ) {
+        if !pull_payouts {
+            self.send_from_escrow(game_id, to, token_id, amount);
+            return;
+        }
+
+        self.withdraw_from_escrow(game_id, amount);
+        self.claimable(to)
+            .entry(token_id.clone())
+            .and_modify(|claimable| *claimable += amount)
+            .or_insert(amount.clone());
+    }
+
+    //escrow
+    fn deposit_to_escrow(&self, game_id: u64, amount: &BigUint) {
+        self.game_escrow(game_id).update(|escrow| *escrow += amount);
+    }
+
+    fn send_from_escrow(
+        &self,
+        game_id: u64,
+        to: &ManagedAddress,
+        token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        self.withdraw_from_escrow(game_id, amount);
+
+        self.tx()
+            .to(to)
+            .egld_or_single_esdt(token_id, 0, amount)
+            .transfer();
+    }
+
+    fn withdraw_from_escrow(&self, game_id: u64, amount: &BigUint) {
+        self.game_escrow(game_id).update(|escrow| {
+            require!(&*escrow >= amount, "payout exceeds game escrow");
+            *escrow -= amount;
+        });
+    }
+
+    //moves the protocol fee from the game escrow to the treasury, returns the prize pool left
+    fn collect_rake(
+        &self,
+        game_id: u64,
+        game_settings: &GameSettings<Self::Api>,
+        total_wager: BigUint,
+    ) -> BigUint {
+        let rake = &total_wager * &BigUint::from(game_settings.rake) / &BigUint::from(DENOM);
+        if rake == 0u64 {
+            return total_wager;
+        }
+
+        self.withdraw_from_escrow(game_id, &rake);
+        self.treasury(&game_settings.token_id)
+            .update(|treasury| *treasury += &rake);
+        self.collect_rake_event(game_id, &game_settings.token_id, &rake);
+
+        total_wager - rake
+    }
+
+    //moves the leave penalty from the game escrow to the collected fees, returns the refund left
+    fn collect_leave_penalty(
+        &self,
+        game_id: u64,
+        game_settings: &GameSettings<Self::Api>,
+    ) -> BigUint {
+        let penalty = &game_settings.wager * &BigUint::from(game_settings.leave_penalty)
+            / &BigUint::from(DENOM);
+        if penalty == 0u64 {
+            return game_settings.wager.clone();
+        }
+
+        self.withdraw_from_escrow(game_id, &penalty);
+        self.collected_fees(&game_settings.token_id)
+            .update(|fees| *fees += &penalty);
+        self.collect_fees_event(game_id, &game_settings.token_id, &penalty);
+
+        &game_settings.wager - &penalty
+    }
+
+    //after a game is settled only the start fee is left in its escrow
+    fn collect_start_fee(&self, game_id: u64, token_id: &EgldOrEsdtTokenIdentifier) {
+        let start_fee = self.game_escrow(game_id).get();
+        if start_fee == 0u64 {
+            return;
         }
+
+        self.withdraw_from_escrow(game_id, &start_fee);
+        self.collected_fees(token_id)
+            .update(|fees| *fees += &start_fee);
+        self.collect_fees_event(game_id, token_id, &start_fee);
+    }
+
+    //owner funds are the treasury and the collected fees, never the game escrows
+    fn withdraw_owner_funds(
+        &self,
+        to: &ManagedAddress,
+        balance_mapper: SingleValueMapper<BigUint>,
+        token_id: &EgldOrEsdtTokenIdentifier,
+        amount_opt: OptionalValue<BigUint>,
+    ) -> BigUint {
+        let balance = balance_mapper.get();
+        let amount = match amount_opt {
+            OptionalValue::Some(val) => val,
+            OptionalValue::None => balance.clone(),
+        };
+
+        require!(amount > 0u64, "nothing to withdraw");
+        require!(amount <= balance, "amount exceeds available balance");
+
+        balance_mapper.set(balance - &amount);
+        self.tx()
+            .to(to)
+            .egld_or_single_esdt(token_id, 0, &amount)
+            .transfer();
+
+        amount
     }
 
-    fn send_back_wager(&self, game_id: u64, wager: &BigUint, token_id: &EgldOrEsdtTokenIdentifier) {
-        for player in self.players(game_id).iter() {
-            self.tx()
-                .to(&player)
-                .egld_or_single_esdt(token_id, 0, wager)
-                .transfer();
+    //every winner must have joined the game, once, and the percentages must add up to 100%
+    fn get_winner_shares(
+        &self,
+        game_id: u64,
+        winners: MultiValueEncoded<(ManagedAddress, u64)>,
+    ) -> ManagedVec<WinnerShare<Self::Api>> {
+        let players = self.players(game_id);
+        let mut winner_shares: ManagedVec<WinnerShare<Self::Api>> = ManagedVec::new();
+        let mut total_percentage = 0u64;
+
+        for (winner, percentage) in winners.into_iter() {
+            require!(
+                percentage > 0u64 && percentage <= DENOM,
+                "invalid winner percentage"
+            );
+            require!(players.contains(&winner), "winner has not joined the game");
+            require!(
+                !winner_shares.iter().any(|share| share.winner == winner),
+                "duplicate winner"
+            );
+
+            total_percentage += percentage;
+            winner_shares.push(WinnerShare { winner, percentage });
         }
+
+        require!(
+            total_percentage == DENOM,
+            "winners percentages must add up to 100%"
+        );
+
+        winner_shares
+    }
+
+    //splits the prize pool between the winners, the rounding dust goes to the first winner
+    fn get_rewards(
+        &self,
+        total_wager: &BigUint,
+        winner_shares: &ManagedVec<WinnerShare<Self::Api>>,
+    ) -> ManagedVec<Reward<Self::Api>> {
+        let mut rewards: ManagedVec<Reward<Self::Api>> = ManagedVec::new();
+        let mut total_reward = BigUint::zero();
+
+        for share in winner_shares.iter() {
+            let amount = &BigUint::from(share.percentage) * total_wager / &BigUint::from(DENOM);
+            total_reward += &amount;
+            rewards.push(Reward {
+                winner: share.winner,
+                amount,
+            });
+        }
+
+        let dust = total_wager - &total_reward;
+        if dust > 0u64 {
+            let mut first_reward = rewards.get(0);
+            first_reward.amount += dust;
+            let _ = rewards.set(0, &first_reward);
+        }
+
+        rewards
     }
 
     //requires
     fn validate_create_game_payment(
         &self,
-        token_id: &TokenIdentifier,
+        token_id: &EgldOrEsdtTokenIdentifier,
         amount: &BigUint,
         wager: &BigUint,
         waiting_time: u64,
-    ) {
+        number_of_players_min: u64,
+        number_of_players_max: u64,
+    ) -> (u64, u64) {
+        self.require_no_emergency();
         require!(wager > &BigUint::zero(), "wager can't be 0");
         require!(waiting_time > 0u64, "waiting time can't be 0");
 
-        let approved_token_id = self.token_id().get();
-        let start_fee = self.game_start_fee().get();
+        require!(self.accepted_tokens().contains(token_id), "wrong token id");
 
-        require!(token_id == &approved_token_id, "wrong token id");
+        let limits = self.get_game_limits(token_id.clone());
+        require!(wager >= &limits.wager_min, "wager too low");
+        require!(
+            limits.wager_max == 0u64 || wager <= &limits.wager_max,
+            "wager too high"
+        );
+
+        let start_fee = self.get_start_fee(token_id);
         require!(amount == &start_fee, "start game payment amount not right");
+
+        require!(
+            waiting_time >= limits.waiting_time_min,
+            "waiting time too short"
+        );
+        require!(
+            limits.waiting_time_max == 0 || waiting_time <= limits.waiting_time_max,
+            "waiting time too long"
+        );
+
+        let (min, max) = self.get_min_max(number_of_players_min, number_of_players_max);
+        require!(
+            min >= limits.number_of_players_min,
+            "min number of players too low"
+        );
+        require!(
+            limits.number_of_players_max == 0 || max <= limits.number_of_players_max,
+            "max number of players too high"
+        );
+
+        (min, max)
+    }
+
+    fn get_game_limits(&self, token_id: EgldOrEsdtTokenIdentifier) -> GameLimits<Self::Api> {
+        GameLimits {
+            waiting_time_min: self.min_waiting_time().get(),
+            waiting_time_max: self.max_waiting_time().get(),
+            number_of_players_min: self.min_players().get(),
+            number_of_players_max: self.max_players().get(),
+            wager_min: self.token_min_wager(&token_id).get(),
+            wager_max: self.token_max_wager(&token_id).get(),
+        }
+    }
+
+    //a join deadline replaces the waiting time, both have to be within the owner's bounds
+    fn get_time_limit(&self, waiting_time: u64, join_deadline: u64, start_time: u64) -> u64 {
+        let now = self.blockchain().get_block_timestamp();
+        let time_limit = if join_deadline == 0 {
+            now + waiting_time
+        } else {
+            require!(
+                waiting_time == 0,
+                "waiting time and join deadline can't both be set"
+            );
+            self.require_within_schedule_bounds(now, join_deadline);
+            join_deadline
+        };
+
+        if start_time > 0 {
+            require!(
+                start_time >= time_limit,
+                "start time can't be before the join deadline"
+            );
+            self.require_within_schedule_bounds(now, start_time);
+        }
+
+        time_limit
+    }
+
+    fn require_within_schedule_bounds(&self, now: u64, timestamp: u64) {
+        require!(timestamp > now, "timestamp is in the past");
+
+        let lead = timestamp - now;
+        require!(
+            lead >= self.min_schedule_lead().get(),
+            "timestamp is too soon"
+        );
+
+        let max_lead = self.max_schedule_lead().get();
+        require!(max_lead == 0 || lead <= max_lead, "timestamp is too far");
+    }
+
+    //the deadlines of a scheduled game run from its start time
+    fn get_game_start(&self, game_settings: &GameSettings<Self::Api>) -> u64 {
+        core::cmp::max(game_settings.time_limit, game_settings.start_time)
     }
 
     fn validate_join_game(
         &self,
         caller: &ManagedAddress,
         now: u64,
-        token_id: &TokenIdentifier,
+        token_id: &EgldOrEsdtTokenIdentifier,
         amount: &BigUint,
         game_id: u64,
     ) -> GameSettings<Self::Api> {
+        require!(self.game_result(game_id).is_empty(), "game is not open");
         require!(
             !self.game_settings(game_id).is_empty(),
             "no settings for game id"
         );
         let game_settings = self.game_settings(game_id).get();
-        let accepted_token_id = self.token_id().get();
+        self.require_no_emergency();
 
         require!(
             !self.games_per_user(caller).contains(&game_id),
@@ -106,13 +727,22 @@ pub trait PrivateModule: crate::storage::StorageModule {
             "max number of players reached"
         );
 
-        require!(token_id == &accepted_token_id, "wrong token sent");
+        require!(
+            game_settings.status == Status::Open || game_settings.status == Status::Ready,
+            "game is not open"
+        );
+
+        require!(token_id == &game_settings.token_id, "wrong token sent");
         require!(amount == &game_settings.wager, "wrong amount paid");
 
         game_settings
     }
 
-    fn validate_claim_wager(&self, caller: &ManagedAddress, game_id: u64) -> BigUint {
+    fn validate_leave_game(
+        &self,
+        caller: &ManagedAddress,
+        game_id: u64,
+    ) -> GameSettings<Self::Api> {
         require!(
             !self.game_settings(game_id).is_empty(),
             "no settings for game id"
@@ -126,26 +756,73 @@ pub trait PrivateModule: crate::storage::StorageModule {
         let game_settings = self.game_settings(game_id).get();
         let now = self.blockchain().get_block_timestamp();
 
+        require!(now <= game_settings.time_limit, "waiting time has passed");
+
         require!(
-            now > game_settings.time_limit,
-            "waiting time is not over yet"
+            game_settings.status == Status::Open || game_settings.status == Status::Ready,
+            "game is not open"
+        );
+
+        game_settings
+    }
+
+    fn validate_cancel_game(
+        &self,
+        caller: &ManagedAddress,
+        game_id: u64,
+    ) -> GameSettings<Self::Api> {
+        require!(self.game_result(game_id).is_empty(), "game is not open");
+        require!(
+            !self.game_settings(game_id).is_empty(),
+            "no settings for game id"
         );
 
+        let game_settings = self.game_settings(game_id).get();
+
+        require!(
+            caller == &game_settings.creator || self.admins().contains(caller),
+            "only the creator or an admin can cancel the game"
+        );
+
+        let status = self.get_game_status(&game_settings);
         require!(
-            game_settings.status == Status::Invalid,
-            "can manually claim back wager only if the game is invalid"
+            status == Status::Open || status == Status::Ready,
+            "game is not open"
         );
 
-        game_settings.wager
+        game_settings
     }
 
-    fn validate_send_reward(&self, game_id: u64) -> GameSettings<Self::Api> {
+    fn validate_claim_wager(
+        &self,
+        caller: &ManagedAddress,
+        game_id: u64,
+    ) -> GameSettings<Self::Api> {
+        require!(
+            self.game_result(game_id).is_empty(),
+            "can manually claim back wager only if the game is open"
+        );
+
         require!(
             !self.game_settings(game_id).is_empty(),
             "no settings for game id"
         );
 
+        //the players are removed while the game is settled or refunded
         let game_settings = self.game_settings(game_id).get();
+        require!(
+            matches!(
+                game_settings.status,
+                Status::Open | Status::Ready | Status::InProgress
+            ),
+            "can manually claim back wager only if the game is open"
+        );
+
+        require!(
+            self.games_per_user(caller).contains(&game_id),
+            "caller has not joined the game"
+        );
+
         let now = self.blockchain().get_block_timestamp();
 
         require!(
@@ -153,14 +830,170 @@ pub trait PrivateModule: crate::storage::StorageModule {
             "waiting time is not over yet"
         );
 
+        //a valid game can only be left once it missed its settlement deadline
+        if game_settings.status != Status::Open {
+            require!(
+                game_settings.settlement_deadline > 0,
+                "can manually claim back wager only if the game is open"
+            );
+            require!(
+                now > game_settings.settlement_deadline,
+                "settlement deadline is not over yet"
+            );
+        }
+
+        game_settings
+    }
+
+    fn validate_send_reward(&self, game_id: u64) -> GameSettings<Self::Api> {
+        if !self.game_result(game_id).is_empty() {
+            match self.game_result(game_id).get().status {
+                Status::Cancelled => sc_panic!("game was cancelled"),
+                Status::Refunded => sc_panic!("game already refunded"),
+                _ => sc_panic!("game already settled"),
+            }
+        }
+        require!(
+            self.pending_result(game_id).is_empty(),
+            "result already submitted"
+        );
+        self.require_no_emergency();
+
+        require!(
+            !self.game_settings(game_id).is_empty(),
+            "no settings for game id"
+        );
+
+        let mut game_settings = self.game_settings(game_id).get();
+        game_settings.status = self.get_game_status(&game_settings);
+        require!(
+            !self.is_settlement_deadline_over(&game_settings),
+            "settlement deadline has passed"
+        );
+
+        match game_settings.status {
+            Status::Open | Status::Ready => {
+                let now = self.blockchain().get_block_timestamp();
+                require!(
+                    now > game_settings.time_limit,
+                    "waiting time is not over yet"
+                );
+            }
+            Status::InProgress => {
+                let now = self.blockchain().get_block_timestamp();
+                require!(now >= game_settings.start_time, "game has not started yet");
+            }
+            Status::Settled => sc_panic!("game already settled"),
+            Status::Cancelled => sc_panic!("game was cancelled"),
+            Status::Refunded => sc_panic!("game already refunded"),
+            Status::Settling => sc_panic!("settlement already started"),
+        }
+
+        game_settings
+    }
+
+    //the games whose settlement has not started yet, or has not processed every player
+    fn validate_emergency_withdraw(&self, game_id: u64) -> GameSettings<Self::Api> {
+        require!(
+            self.game_result(game_id).is_empty(),
+            "game is already closed"
+        );
+        require!(
+            !self.game_settings(game_id).is_empty(),
+            "no settings for game id"
+        );
+
+        let game_settings = self.game_settings(game_id).get();
+        require!(
+            matches!(
+                game_settings.status,
+                Status::Open | Status::Ready | Status::InProgress | Status::Settling
+            ),
+            "game is already closed"
+        );
+
         game_settings
     }
 
-    fn require_enabled(&self) {
-        require!(!self.enabled().is_empty(), "maintenance")
+    fn validate_pending_result(&self, game_id: u64) -> PendingResult<Self::Api> {
+        require!(
+            !self.pending_result(game_id).is_empty(),
+            "no pending result for game id"
+        );
+
+        self.pending_result(game_id).get()
+    }
+
+    fn validate_settlement_proposal(&self, game_id: u64) -> SettlementProposal<Self::Api> {
+        require!(
+            !self.settlement_proposal(game_id).is_empty(),
+            "no settlement proposal for game id"
+        );
+
+        let proposal = self.settlement_proposal(game_id).get();
+        let now = self.blockchain().get_block_timestamp();
+        require!(now <= proposal.expires_at, "settlement proposal expired");
+
+        proposal
+    }
+
+    //the owner can act as any role except the settler, so the admin quorum can't be bypassed
+    fn require_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        if role != Role::Settler && caller == self.blockchain().get_owner_address() {
+            return;
+        }
+
+        require!(
+            self.has_role(&caller, &role),
+            "caller does not have the required role"
+        );
+    }
+
+    fn require_no_emergency(&self) {
+        require!(!self.emergency_mode().get(), "emergency mode");
+    }
+
+    //maintenance pauses every operation except the refunds and claims
+    fn require_not_paused(&self, operation: Operation) {
+        require!(
+            !self.is_legacy_migration_pending(),
+            "legacy games not migrated yet"
+        );
+        require!(
+            operation == Operation::Claims || !self.enabled().is_empty(),
+            "maintenance"
+        );
+        require!(!self.paused(&operation).get(), "operation paused");
     }
 
     //helper
+    //the signed message binds the result to this contract and game:
+    //sc address | game id | nonce | (winner address | percentage)*, numbers as big endian u64
+    fn get_result_message(
+        &self,
+        game_id: u64,
+        nonce: u64,
+        winners: &OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>>,
+    ) -> ManagedBuffer {
+        let mut message = self
+            .blockchain()
+            .get_sc_address()
+            .as_managed_buffer()
+            .clone();
+        message.append_bytes(&game_id.to_be_bytes());
+        message.append_bytes(&nonce.to_be_bytes());
+
+        if let OptionalValue::Some(val) = winners {
+            for (winner, percentage) in val.clone().into_iter() {
+                message.append(winner.as_managed_buffer());
+                message.append_bytes(&percentage.to_be_bytes());
+            }
+        }
+
+        message
+    }
+
     fn get_new_game_id(&self) -> u64 {
         if self.last_game_id().is_empty() {
             return 1u64;
@@ -169,6 +1002,104 @@ pub trait PrivateModule: crate::storage::StorageModule {
         last_id + 1u64
     }
 
+    fn get_settlement_batch_size(&self) -> usize {
+        if self.settlement_batch_size().is_empty() {
+            return DEFAULT_SETTLEMENT_BATCH_SIZE;
+        }
+
+        self.settlement_batch_size().get()
+    }
+
+    fn get_proposal_duration(&self) -> u64 {
+        if self.proposal_duration().is_empty() {
+            return DEFAULT_PROPOSAL_DURATION;
+        }
+
+        self.proposal_duration().get()
+    }
+
+    //payouts above the threshold of the game token need the approval of several admins
+    fn requires_quorum(&self, game_id: u64, game_settings: &GameSettings<Self::Api>) -> bool {
+        let threshold = self.quorum_threshold(&game_settings.token_id);
+        if threshold.is_empty() || self.required_approvals().get() <= 1 {
+            return false;
+        }
+
+        let total_wager = &BigUint::from(self.players(game_id).len() as u64) * &game_settings.wager;
+        total_wager > threshold.get()
+    }
+
+    //approvals of removed admins don't count
+    fn count_approvals(&self, proposal: &SettlementProposal<Self::Api>) -> usize {
+        proposal
+            .approvers
+            .iter()
+            .filter(|approver| self.admins().contains(approver))
+            .count()
+    }
+
+    //no winners means a tie/draw
+    fn get_winners_arg(
+        &self,
+        winner_shares: &ManagedVec<WinnerShare<Self::Api>>,
+    ) -> OptionalValue<MultiValueEncoded<(ManagedAddress, u64)>> {
+        if winner_shares.is_empty() {
+            return OptionalValue::None;
+        }
+
+        let mut winners = MultiValueEncoded::new();
+        for share in winner_shares.iter() {
+            winners.push((share.winner, share.percentage));
+        }
+
+        OptionalValue::Some(winners)
+    }
+
+    fn is_paused(&self, operation: &Operation) -> bool {
+        if operation != &Operation::Claims && self.enabled().is_empty() {
+            return true;
+        }
+
+        self.paused(operation).get()
+    }
+
+    //the players of a valid game can claim back their wager after the deadline
+    fn is_settlement_deadline_over(&self, game_settings: &GameSettings<Self::Api>) -> bool {
+        if game_settings.settlement_deadline == 0 || game_settings.status == Status::Open {
+            return false;
+        }
+
+        let now = self.blockchain().get_block_timestamp();
+        now > game_settings.settlement_deadline
+    }
+
+    //a valid game left unsettled for too long after the waiting time
+    fn is_emergency_timeout_over(&self, game_settings: &GameSettings<Self::Api>) -> bool {
+        let emergency_timeout = self.emergency_timeout().get();
+        if emergency_timeout == 0 || game_settings.status == Status::Open {
+            return false;
+        }
+
+        let now = self.blockchain().get_block_timestamp();
+        now > self.get_game_start(game_settings) + emergency_timeout
+    }
+
+    fn has_role(&self, user: &ManagedAddress, role: &Role) -> bool {
+        match role {
+            Role::Settler => self.admins().contains(user),
+            _ => self.roles(user).contains(role),
+        }
+    }
+
+    //the token specific start fee, if set, overrides the general one
+    fn get_start_fee(&self, token_id: &EgldOrEsdtTokenIdentifier) -> BigUint {
+        if self.token_start_fee(token_id).is_empty() {
+            return self.game_start_fee().get();
+        }
+
+        self.token_start_fee(token_id).get()
+    }
+
     fn get_min_max(&self, a: u64, b: u64) -> (u64, u64) {
         require!(a != 0u64 && b != 0u64, "number of players cannot be 0");
 
@@ -1,6 +1,9 @@
 use multiversx_sc::imports::*;
 
-use crate::types::GameSettings;
+use crate::types::{
+    GameResult, GameSettings, LegacyGameSettings, Operation, PendingResult, Role, Settlement,
+    SettlementProposal,
+};
 
 #[multiversx_sc::module]
 pub trait StorageModule {
@@ -13,10 +16,127 @@ pub trait StorageModule {
     #[storage_mapper("gameStartFee")]
     fn game_start_fee(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getAcceptedTokens)]
+    #[storage_mapper("acceptedTokens")]
+    fn accepted_tokens(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[view(getTokenMinWager)]
+    #[storage_mapper("tokenMinWager")]
+    fn token_min_wager(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getTokenMaxWager)]
+    #[storage_mapper("tokenMaxWager")]
+    fn token_max_wager(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getTokenStartFee)]
+    #[storage_mapper("tokenStartFee")]
+    fn token_start_fee(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getRake)]
+    #[storage_mapper("rake")]
+    fn rake(&self) -> SingleValueMapper<u64>;
+
+    #[view(getLeavePenalty)]
+    #[storage_mapper("leavePenalty")]
+    fn leave_penalty(&self) -> SingleValueMapper<u64>;
+
+    #[view(getForfeitStartFeeOnCancel)]
+    #[storage_mapper("forfeitStartFeeOnCancel")]
+    fn forfeit_start_fee_on_cancel(&self) -> SingleValueMapper<bool>;
+
+    #[view(getTreasury)]
+    #[storage_mapper("treasury")]
+    fn treasury(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getCollectedFees)]
+    #[storage_mapper("collectedFees")]
+    fn collected_fees(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    //bounds of the game parameters chosen by the creators, no max if 0
+    #[view(getMinWaitingTime)]
+    #[storage_mapper("minWaitingTime")]
+    fn min_waiting_time(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMaxWaitingTime)]
+    #[storage_mapper("maxWaitingTime")]
+    fn max_waiting_time(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMinPlayers)]
+    #[storage_mapper("minPlayers")]
+    fn min_players(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMaxPlayers)]
+    #[storage_mapper("maxPlayers")]
+    fn max_players(&self) -> SingleValueMapper<u64>;
+
+    //seconds from the creation a join deadline or start time can be set at, no max if 0
+    #[view(getMinScheduleLead)]
+    #[storage_mapper("minScheduleLead")]
+    fn min_schedule_lead(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMaxScheduleLead)]
+    #[storage_mapper("maxScheduleLead")]
+    fn max_schedule_lead(&self) -> SingleValueMapper<u64>;
+
+    //seconds after the waiting time a valid game has to be settled, 0 if there is no deadline
+    #[view(getSettlementTimeout)]
+    #[storage_mapper("settlementTimeout")]
+    fn settlement_timeout(&self) -> SingleValueMapper<u64>;
+
+    #[view(getSettlementBatchSize)]
+    #[storage_mapper("settlementBatchSize")]
+    fn settlement_batch_size(&self) -> SingleValueMapper<usize>;
+
+    #[view(getPullPayouts)]
+    #[storage_mapper("pullPayouts")]
+    fn pull_payouts(&self) -> SingleValueMapper<bool>;
+
+    //payouts of a bigger total wager need the approval of required_approvals admins
+    #[view(getQuorumThreshold)]
+    #[storage_mapper("quorumThreshold")]
+    fn quorum_threshold(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getRequiredApprovals)]
+    #[storage_mapper("requiredApprovals")]
+    fn required_approvals(&self) -> SingleValueMapper<usize>;
+
+    #[view(getProposalDuration)]
+    #[storage_mapper("proposalDuration")]
+    fn proposal_duration(&self) -> SingleValueMapper<u64>;
+
+    //seconds between the result submission and the payout, 0 pays right away
+    #[view(getChallengePeriod)]
+    #[storage_mapper("challengePeriod")]
+    fn challenge_period(&self) -> SingleValueMapper<u64>;
+
+    //ed25519 public key of the off-chain game server signing the results
+    #[view(getResultSigner)]
+    #[storage_mapper("resultSigner")]
+    fn result_signer(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    //not set by the legacy deployments, see migrateLegacyGames
+    #[view(getStorageVersion)]
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
     #[view(getEnabled)]
     #[storage_mapper("enabled")]
     fn enabled(&self) -> SingleValueMapper<bool>;
 
+    //players and creators can withdraw their funds from the unsettled games
+    #[view(isEmergencyMode)]
+    #[storage_mapper("emergencyMode")]
+    fn emergency_mode(&self) -> SingleValueMapper<bool>;
+
+    //seconds after the waiting time an unsettled valid game turns on the emergency mode, 0 disables it
+    #[view(getEmergencyTimeout)]
+    #[storage_mapper("emergencyTimeout")]
+    fn emergency_timeout(&self) -> SingleValueMapper<u64>;
+
+    #[view(isOperationPaused)]
+    #[storage_mapper("paused")]
+    fn paused(&self, operation: &Operation) -> SingleValueMapper<bool>;
+
     #[storage_mapper("admins")]
     fn admins(&self) -> WhitelistMapper<ManagedAddress>;
 
@@ -25,6 +145,10 @@ pub trait StorageModule {
         self.admins().contains(&user)
     }
 
+    //every role except the settler, which is kept in the admins whitelist
+    #[storage_mapper("roles")]
+    fn roles(&self, user: &ManagedAddress) -> UnorderedSetMapper<Role>;
+
     //GAME
     #[view(getLastGameId)]
     #[storage_mapper("lastGameId")]
@@ -34,15 +158,82 @@ pub trait StorageModule {
     #[storage_mapper("gameSettings")]
     fn game_settings(&self, game_id: u64) -> SingleValueMapper<GameSettings<Self::Api>>;
 
-    #[view(getGameIdBySettings)]
+    //game ids in creation order, the position is the creation nonce (starting with 1)
+    #[view(getGamesByCreator)]
+    #[storage_mapper("gamesByCreator")]
+    fn games_by_creator(&self, creator: &ManagedAddress) -> VecMapper<u64>;
+
+    //replaced by games_by_creator, only used to clear the old entries during the migration
     #[storage_mapper("gameIdBySettings")]
-    fn game_id(&self, game_settings: &GameSettings<Self::Api>) -> SingleValueMapper<u64>;
+    fn legacy_game_id(
+        &self,
+        game_settings: &LegacyGameSettings<Self::Api>,
+    ) -> SingleValueMapper<u64>;
+
+    //same key as game_settings, only read for the legacy games not migrated yet
+    #[storage_mapper("gameSettings")]
+    fn legacy_game_settings(
+        &self,
+        game_id: u64,
+    ) -> SingleValueMapper<LegacyGameSettings<Self::Api>>;
+
+    //the legacy games are migrated in ascending order, up to the last id before the upgrade
+    #[view(getLegacyLastGameId)]
+    #[storage_mapper("legacyLastGameId")]
+    fn legacy_last_game_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getLegacyMigratedGameId)]
+    #[storage_mapper("legacyMigratedGameId")]
+    fn legacy_migrated_game_id(&self) -> SingleValueMapper<u64>;
+
+    //games accepting players, removed once the game is full, settled, refunded or cancelled
+    #[view(getOpenGameIds)]
+    #[storage_mapper("openGames")]
+    fn open_games(&self) -> UnorderedSetMapper<u64>;
+
+    #[view(getSettlement)]
+    #[storage_mapper("settlement")]
+    fn settlement(&self, game_id: u64) -> SingleValueMapper<Settlement<Self::Api>>;
+
+    #[view(getPendingResult)]
+    #[storage_mapper("pendingResult")]
+    fn pending_result(&self, game_id: u64) -> SingleValueMapper<PendingResult<Self::Api>>;
+
+    #[view(getSettlementProposal)]
+    #[storage_mapper("settlementProposal")]
+    fn settlement_proposal(&self, game_id: u64)
+        -> SingleValueMapper<SettlementProposal<Self::Api>>;
+
+    //games with a settlement proposal waiting for approvals
+    #[storage_mapper("pendingProposals")]
+    fn pending_proposals(&self) -> UnorderedSetMapper<u64>;
+
+    //number of signed results submitted for the game, part of the next signed message
+    #[view(getSignedResultNonce)]
+    #[storage_mapper("signedResultNonce")]
+    fn signed_result_nonce(&self, game_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("pendingReward")]
+    fn pending_reward(&self, game_id: u64, winner: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getGameResult)]
+    #[storage_mapper("gameResult")]
+    fn game_result(&self, game_id: u64) -> SingleValueMapper<GameResult<Self::Api>>;
+
+    #[view(getGameEscrow)]
+    #[storage_mapper("gameEscrow")]
+    fn game_escrow(&self, game_id: u64) -> SingleValueMapper<BigUint>;
 
     #[view(getPlayers)]
     #[storage_mapper("players")]
     fn players(&self, game_id: u64) -> UnorderedSetMapper<ManagedAddress>;
 
     //USERS
+    //rewards and refunds kept by the contract until the user claims them
+    #[view(getClaimable)]
+    #[storage_mapper("claimable")]
+    fn claimable(&self, user: &ManagedAddress) -> MapMapper<EgldOrEsdtTokenIdentifier, BigUint>;
+
     #[view(getGamesPerUser)]
     #[storage_mapper("gamesPerUser")]
     fn games_per_user(&self, user: &ManagedAddress) -> UnorderedSetMapper<u64>;
@@ -6,8 +6,13 @@ use multiversx_sc::imports::*;
     TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
 )]
 pub enum Status {
-    Valid,
-    Invalid,
+    Open,       //accepting players, min number of players not reached yet
+    Ready,      //min number of players reached, still accepting players
+    InProgress, //max number of players reached or waiting time over with enough players
+    Settled,    //rewards sent or wagers returned after a tie/draw
+    Cancelled,  //closed before it started
+    Refunded,   //not enough players, wagers and start fee returned
+    Settling,   //settlement started, not every player has been processed yet
 }
 
 #[type_abi]
@@ -15,10 +20,161 @@ pub enum Status {
     TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
 )]
 pub struct GameSettings<M: ManagedTypeApi> {
-    pub time_limit: u64,            //start_time + waiting time
+    pub time_limit: u64, //creation time + waiting time, or the join deadline
     pub number_of_players_min: u64, //min and max
     pub number_of_players_max: u64,
     pub wager: BigUint<M>,
+    pub token_id: EgldOrEsdtTokenIdentifier<M>, //token used for the start fee, wagers and rewards
+    pub rake: u64, //protocol fee * 100 at creation time, taken from the prize pool
+    pub leave_penalty: u64, //percentage * 100 of the wager kept when a player leaves the game
+    pub forfeit_start_fee: bool, //the start fee is kept as a fee if the game is cancelled
+    pub settlement_deadline: u64, //players can claim back their wager afterwards, 0 if none
+    pub join_deadline: u64, //absolute join deadline set by the creator, 0 if the waiting time was used
+    pub start_time: u64,    //scheduled start set by the creator, 0 if none
     pub creator: ManagedAddress<M>,
     pub status: Status,
 }
+
+//layout of the games created before the upgrade, migrated by migrateLegacyGames
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub enum LegacyStatus {
+    Valid,   //the min number of players was reached
+    Invalid, //status at creation
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct LegacyGameSettings<M: ManagedTypeApi> {
+    pub time_limit: u64,
+    pub number_of_players_min: u64,
+    pub number_of_players_max: u64,
+    pub wager: BigUint<M>,
+    pub creator: ManagedAddress<M>,
+    pub status: LegacyStatus,
+}
+
+//operations that can be paused separately, see pauseOperation
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub enum Operation {
+    Creation,   //createGame
+    Joining,    //joinGame
+    Settlement, //sending, approving and finalizing the results
+    Claims,     //refunds and claims, still available during maintenance
+}
+
+//the owner grants and revokes the roles, and can act as any role except the settler
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub enum Role {
+    ConfigManager, //token, fee and game settings
+    Settler,       //settles the games, same as the admins whitelist
+    Pauser,        //enables and disables the contract
+    Treasurer,     //withdraws the rake and the collected fees
+}
+
+//progress of a settlement processed in batches, the players are removed once processed
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct Settlement<M: ManagedTypeApi> {
+    pub status: Status, //Settled, Refunded or Cancelled once every player has been processed
+    pub refund_wagers: bool, //tie/draw or not enough players
+    pub pull_payouts: bool, //payouts are kept in the contract until claimed
+    pub player_count: u64,
+    pub processed: u64,
+    pub rake: BigUint<M>,
+    pub rewards: ManagedVec<M, Reward<M>>,
+}
+
+//compact record kept after a game is closed, instead of its settings and players
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameResult<M: ManagedTypeApi> {
+    pub creator: ManagedAddress<M>,
+    pub token_id: EgldOrEsdtTokenIdentifier<M>,
+    pub wager: BigUint<M>,
+    pub player_count: u64,
+    pub status: Status,                    //Settled, Refunded or Cancelled
+    pub rake: BigUint<M>,                  //amount taken from the prize pool
+    pub rewards: ManagedVec<M, Reward<M>>, //empty after a tie/draw
+    pub settled_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameInfo<M: ManagedTypeApi> {
+    pub game_id: u64,
+    pub game_settings: GameSettings<M>,
+    pub player_count: u64,
+    pub players: ManagedVec<M, ManagedAddress<M>>,
+    pub pool: BigUint<M>,    //funds currently held in the game escrow
+    pub time_remaining: u64, //until the waiting time is over, 0 afterwards
+    pub status: Status,      //current status, taking the waiting time into account
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameFilter<M: ManagedTypeApi> {
+    pub status: Option<Status>, //current status, taking the waiting time into account
+    pub wager_min: Option<BigUint<M>>,
+    pub wager_max: Option<BigUint<M>>,
+    pub creator: Option<ManagedAddress<M>>,
+    pub token_id: Option<EgldOrEsdtTokenIdentifier<M>>,
+}
+
+//bounds of the games created with a token, a max of 0 means no max
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct GameLimits<M: ManagedTypeApi> {
+    pub waiting_time_min: u64,
+    pub waiting_time_max: u64,
+    pub number_of_players_min: u64,
+    pub number_of_players_max: u64,
+    pub wager_min: BigUint<M>,
+    pub wager_max: BigUint<M>,
+}
+
+//settlement of a high value game waiting for the approval of enough admins
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct SettlementProposal<M: ManagedTypeApi> {
+    pub game_id: u64,
+    pub proposer: ManagedAddress<M>,
+    pub winners: ManagedVec<M, WinnerShare<M>>,
+    pub approvers: ManagedVec<M, ManagedAddress<M>>, //the proposer is the first approver
+    pub expires_at: u64,
+}
+
+//result waiting for the end of the challenge period before the payout
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug, PartialEq)]
+pub struct PendingResult<M: ManagedTypeApi> {
+    pub submitted_by: ManagedAddress<M>,
+    pub winners: ManagedVec<M, WinnerShare<M>>, //empty for a tie/draw
+    pub challenge_ends_at: u64,
+    pub disputed_by: Option<ManagedAddress<M>>, //the payout is frozen until the owner resolves it
+    pub dispute_resolved: bool,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub struct WinnerShare<M: ManagedTypeApi> {
+    pub winner: ManagedAddress<M>,
+    pub percentage: u64, //percentage * 100 of the prize pool
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem, Debug, PartialEq,
+)]
+pub struct Reward<M: ManagedTypeApi> {
+    pub winner: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+}
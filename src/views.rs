@@ -0,0 +1,245 @@
+use crate::types::{
+    GameFilter, GameInfo, GameLimits, GameSettings, Operation, Role, SettlementProposal,
+};
+
+use multiversx_sc::imports::*;
+
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[multiversx_sc::module]
+pub trait ViewsModule:
+    crate::private::PrivateModule + crate::storage::StorageModule + crate::events::EventsModule
+{
+    //nonce is the position of the game between the ones created by the same creator, starting with 1
+    #[view(getGameIdByCreator)]
+    fn get_game_id_by_creator(&self, creator: ManagedAddress, nonce: usize) -> u64 {
+        let games = self.games_by_creator(&creator);
+        require!(
+            (1..=games.len()).contains(&nonce),
+            "no game for creator nonce"
+        );
+
+        games.get(nonce)
+    }
+
+    #[view(getGameInfo)]
+    fn get_game_info(&self, game_id: u64) -> GameInfo<Self::Api> {
+        //closed games are archived, their players and escrow are no longer kept
+        if !self.game_result(game_id).is_empty() {
+            let game_result = self.game_result(game_id).get();
+            let game_settings = self.get_archived_settings(&game_result);
+
+            return GameInfo {
+                game_id,
+                player_count: game_result.player_count,
+                players: ManagedVec::new(),
+                pool: BigUint::zero(),
+                time_remaining: 0,
+                status: game_settings.status.clone(),
+                game_settings,
+            };
+        }
+
+        require!(
+            !self.game_settings(game_id).is_empty(),
+            "no settings for game id"
+        );
+
+        let game_settings = self.game_settings(game_id).get();
+        let now = self.blockchain().get_block_timestamp();
+
+        let mut players = ManagedVec::new();
+        for player in self.players(game_id).iter() {
+            players.push(player);
+        }
+
+        GameInfo {
+            game_id,
+            player_count: players.len() as u64,
+            players,
+            pool: self.game_escrow(game_id).get(),
+            time_remaining: game_settings.time_limit.saturating_sub(now),
+            status: self.get_game_status(&game_settings),
+            game_settings,
+        }
+    }
+
+    #[view(getGamesInfo)]
+    fn get_games_info(
+        &self,
+        game_ids: MultiValueEncoded<u64>,
+    ) -> MultiValueEncoded<GameInfo<Self::Api>> {
+        let mut games_info = MultiValueEncoded::new();
+        for game_id in game_ids {
+            games_info.push(self.get_game_info(game_id));
+        }
+
+        games_info
+    }
+
+    //games still accepting players, read from the open games index
+    //offset is the position in the index, the status in the returned settings is the current one
+    #[view(getOpenGames)]
+    fn get_open_games(
+        &self,
+        offset: usize,
+        size: usize,
+        filter: GameFilter<Self::Api>,
+    ) -> MultiValueEncoded<MultiValue2<u64, GameSettings<Self::Api>>> {
+        let now = self.blockchain().get_block_timestamp();
+        let size = core::cmp::min(size, MAX_PAGE_SIZE);
+        let mut games = MultiValueEncoded::new();
+
+        for game_id in self.open_games().iter().skip(offset) {
+            if games.len() == size {
+                break;
+            }
+
+            let mut game_settings = self.game_settings(game_id).get();
+            game_settings.status = self.get_game_status(&game_settings);
+            if now > game_settings.time_limit || !self.matches_filter(&game_settings, &filter) {
+                continue;
+            }
+
+            games.push((game_id, game_settings).into());
+        }
+
+        games
+    }
+
+    //all games, starting with from_game_id, in ascending order
+    //the next page starts after the last returned id
+    #[view(getGames)]
+    fn get_games(
+        &self,
+        from_game_id: u64,
+        size: usize,
+        filter: GameFilter<Self::Api>,
+    ) -> MultiValueEncoded<MultiValue2<u64, GameSettings<Self::Api>>> {
+        let last_game_id = self.last_game_id().get();
+        let size = core::cmp::min(size, MAX_PAGE_SIZE);
+        let mut games = MultiValueEncoded::new();
+
+        for game_id in core::cmp::max(from_game_id, 1u64)..=last_game_id {
+            if games.len() == size {
+                break;
+            }
+
+            //closed games are archived, see getGameResult
+            let game_settings = if self.game_settings(game_id).is_empty() {
+                if self.game_result(game_id).is_empty() {
+                    continue;
+                }
+
+                self.get_archived_settings(&self.game_result(game_id).get())
+            } else {
+                let mut game_settings = self.game_settings(game_id).get();
+                game_settings.status = self.get_game_status(&game_settings);
+                game_settings
+            };
+
+            if !self.matches_filter(&game_settings, &filter) {
+                continue;
+            }
+
+            games.push((game_id, game_settings).into());
+        }
+
+        games
+    }
+
+    //whether each operation is currently paused, taking the maintenance into account
+    #[view(getPauseMatrix)]
+    fn get_pause_matrix(&self) -> MultiValueEncoded<MultiValue2<Operation, bool>> {
+        let mut pause_matrix = MultiValueEncoded::new();
+        for operation in [
+            Operation::Creation,
+            Operation::Joining,
+            Operation::Settlement,
+            Operation::Claims,
+        ] {
+            let paused = self.is_paused(&operation);
+            pause_matrix.push((operation, paused).into());
+        }
+
+        pause_matrix
+    }
+
+    //bounds checked when a game is created with the token
+    #[view(getGameLimits)]
+    fn game_limits(&self, token_id: EgldOrEsdtTokenIdentifier) -> GameLimits<Self::Api> {
+        self.get_game_limits(token_id)
+    }
+
+    //the roles granted to the user, the settler role is granted to the admins
+    #[view(getRoles)]
+    fn get_roles(&self, user: ManagedAddress) -> MultiValueEncoded<Role> {
+        let mut roles = MultiValueEncoded::new();
+        for role in [
+            Role::ConfigManager,
+            Role::Settler,
+            Role::Pauser,
+            Role::Treasurer,
+        ] {
+            if self.has_role(&user, &role) {
+                roles.push(role);
+            }
+        }
+
+        roles
+    }
+
+    //settlement proposals waiting for approvals, including the expired ones not removed yet
+    #[view(getPendingProposals)]
+    fn get_pending_proposals(&self) -> MultiValueEncoded<SettlementProposal<Self::Api>> {
+        let mut proposals = MultiValueEncoded::new();
+        for game_id in self.pending_proposals().iter() {
+            proposals.push(self.settlement_proposal(game_id).get());
+        }
+
+        proposals
+    }
+
+    #[view(getProposalApprovers)]
+    fn get_proposal_approvers(&self, game_id: u64) -> MultiValueEncoded<ManagedAddress> {
+        require!(
+            !self.settlement_proposal(game_id).is_empty(),
+            "no settlement proposal for game id"
+        );
+
+        self.settlement_proposal(game_id)
+            .get()
+            .approvers
+            .into_iter()
+            .collect()
+    }
+
+    fn matches_filter(
+        &self,
+        game_settings: &GameSettings<Self::Api>,
+        filter: &GameFilter<Self::Api>,
+    ) -> bool {
+        let status_matches = match &filter.status {
+            Some(status) => &game_settings.status == status,
+            None => true,
+        };
+        let wager_min_matches = match &filter.wager_min {
+            Some(wager_min) => &game_settings.wager >= wager_min,
+            None => true,
+        };
+        let wager_max_matches = match &filter.wager_max {
+            Some(wager_max) => &game_settings.wager <= wager_max,
+            None => true,
+        };
+        let creator_matches = match &filter.creator {
+            Some(creator) => &game_settings.creator == creator,
+            None => true,
+        };
+        let token_matches = match &filter.token_id {
+            Some(token_id) => &game_settings.token_id == token_id,
+            None => true,
+        };
+
+        status_matches && wager_min_matches && wager_max_matches && creator_matches && token_matches
+    }
+}
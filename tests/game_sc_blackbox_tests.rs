@@ -1,12 +1,14 @@
+use ed25519_dalek::Signer;
 use imports::{
-    MxscPath, ReturnsResult, ReturnsResultUnmanaged, TestAddress, TestSCAddress,
-    TestTokenIdentifier,
+    InterpretableFrom, InterpreterContext, MxscPath, ReturnsResult, ReturnsResultUnmanaged,
+    TestAddress, TestSCAddress, TestTokenIdentifier,
 };
 use multiversx_sc::{
     codec::multi_types::OptionalValue,
     storage::mappers::SingleValue,
     types::{
-        BigUint, EgldOrEsdtTokenIdentifier, ManagedAddress, MultiValueEncoded, TokenIdentifier,
+        AnnotatedValue, BigUint, EgldOrEsdtTokenIdentifier, ManagedAddress, MultiValueEncoded,
+        RHListItem, RHListItemExec, TxEnv,
     },
 };
 use multiversx_sc_scenario::{api::StaticApi, scenario_model::*, *};
@@ -18,6 +20,8 @@ const GAME_SC_PATH: MxscPath = MxscPath::new("output/mvx-game-sc.mxsc.json");
 const BALANCE: u64 = 100_000_000u64;
 const TOKEN_GAME: TestTokenIdentifier = TestTokenIdentifier::new("GAME-123456");
 const TOKEN_GAME_ID: &[u8] = b"GAME-123456";
+const TOKEN_OTHER: TestTokenIdentifier = TestTokenIdentifier::new("OTHER-123456");
+const TOKEN_OTHER_ID: &[u8] = b"OTHER-123456";
 const STARTING_FEE: u64 = 20u64;
 const USER1_ADDR: TestAddress = TestAddress::new("user1");
 const USER2_ADDR: TestAddress = TestAddress::new("user2");
@@ -27,6 +31,85 @@ const USER5_ADDR: TestAddress = TestAddress::new("user5");
 const OWNER_ADDR: TestAddress = TestAddress::new("owner");
 const GAME_SC_ADDR: TestSCAddress = TestSCAddress::new("mvx_game_sc");
 
+// checks the events logged by the transaction, in order, ignoring any extra logs at the end
+struct ExpectLogs(Vec<CheckLog>);
+
+impl<Env, Original> RHListItem<Env, Original> for ExpectLogs
+where
+    Env: TxEnv,
+{
+    type Returns = ();
+}
+
+impl<Env, Original> RHListItemExec<TxResponse, Env, Original> for ExpectLogs
+where
+    Env: TxEnv<RHExpect = TxExpect>,
+{
+    fn item_tx_expect(&self, mut prev: TxExpect) -> TxExpect {
+        prev.logs = CheckLogs::List(CheckLogList {
+            list: self.0.clone(),
+            more_allowed_at_end: true,
+        });
+        prev
+    }
+
+    fn item_process_result(self, _: &TxResponse) -> Self::Returns {}
+}
+
+fn event_log(identifier: &str, topics: Vec<Vec<u8>>, data: Option<Vec<u8>>) -> CheckLog {
+    let mut expected_topics = vec![CheckValue::Equal(BytesValue::from(identifier.as_bytes()))];
+    for topic in topics {
+        expected_topics.push(CheckValue::Equal(BytesValue::from(topic)));
+    }
+
+    CheckLog {
+        address: CheckValue::Equal(BytesValue::from(GAME_SC_ADDR.eval_to_array().as_slice())),
+        endpoint: CheckValue::Star,
+        topics: CheckValue::Equal(expected_topics),
+        data: match data {
+            Some(data) => CheckValue::Equal(vec![CheckValue::Equal(BytesValue::from(data))]),
+            None => CheckValue::Star,
+        },
+    }
+}
+
+// logs of the transfers made by the contract, not checked
+fn transfer_log() -> CheckLog {
+    CheckLog {
+        address: CheckValue::Star,
+        endpoint: CheckValue::Star,
+        topics: CheckValue::Star,
+        data: CheckValue::Star,
+    }
+}
+
+// signs the result the way the game server does: sc address | game id | nonce | (winner | percentage)*
+fn sign_result(
+    secret: [u8; 32],
+    game_id: u64,
+    nonce: u64,
+    winners: &[(TestAddress, u64)],
+) -> Vec<u8> {
+    let secret = ed25519_dalek::SecretKey::from_bytes(&secret).unwrap();
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    let keypair = ed25519_dalek::Keypair { secret, public };
+
+    let mut message = GAME_SC_ADDR.eval_to_array().to_vec();
+    message.extend_from_slice(&game_id.to_be_bytes());
+    message.extend_from_slice(&nonce.to_be_bytes());
+    for (winner, percentage) in winners {
+        message.extend_from_slice(&winner.eval_to_array());
+        message.extend_from_slice(&percentage.to_be_bytes());
+    }
+
+    keypair.sign(&message).to_bytes().to_vec()
+}
+
+fn signer_public_key(secret: [u8; 32]) -> Vec<u8> {
+    let secret = ed25519_dalek::SecretKey::from_bytes(&secret).unwrap();
+    ed25519_dalek::PublicKey::from(&secret).to_bytes().to_vec()
+}
+
 fn world() -> ScenarioWorld {
     let mut blockchain = ScenarioWorld::new();
 
@@ -36,10 +119,19 @@ fn world() -> ScenarioWorld {
 
 struct GameContractState {
     world: ScenarioWorld,
+    token_id: EgldOrEsdtTokenIdentifier<StaticApi>,
 }
 
 impl GameContractState {
     fn new() -> Self {
+        Self::new_with_token(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+    }
+
+    fn new_egld() -> Self {
+        Self::new_with_token(EgldOrEsdtTokenIdentifier::egld())
+    }
+
+    fn new_with_token(token_id: EgldOrEsdtTokenIdentifier<StaticApi>) -> Self {
         let mut world = world();
         world.start_trace();
 
@@ -47,39 +139,45 @@ impl GameContractState {
             .account(OWNER_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
         world
             .account(USER1_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
         world
             .account(USER2_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
         world
             .account(USER3_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
         world
             .account(USER4_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
         world
             .account(USER5_ADDR)
             .nonce(1)
             .balance(BALANCE)
-            .esdt_balance(TOKEN_GAME, BALANCE);
+            .esdt_balance(TOKEN_GAME, BALANCE)
+            .esdt_balance(TOKEN_OTHER, BALANCE);
 
-        Self { world }
+        Self { world, token_id }
     }
 
     fn deploy(&mut self) -> &mut Self {
@@ -90,9 +188,7 @@ impl GameContractState {
             .init(
                 OptionalValue::Some(true),
                 OptionalValue::Some(BigUint::from(STARTING_FEE)),
-                OptionalValue::Some(EgldOrEsdtTokenIdentifier::esdt(TokenIdentifier::from(
-                    TOKEN_GAME_ID,
-                ))),
+                OptionalValue::Some(self.token_id.clone()),
             )
             .code(GAME_SC_PATH)
             .new_address(GAME_SC_ADDR)
@@ -101,6 +197,30 @@ impl GameContractState {
         self
     }
 
+    // contract deployed by a legacy version, with the given storage and balance
+    fn deploy_legacy(&mut self, storage: &[(String, String)], balance: u64) -> &mut Self {
+        let context = self.world.interpreter_context();
+        let mut account = Account::new()
+            .owner(OWNER_ADDR.eval_to_expr().as_str())
+            .esdt_balance(TOKEN_GAME.eval_to_expr().as_str(), balance);
+        account.code = Some(BytesValue::interpret_from(
+            GAME_SC_PATH.eval_to_expr().as_str(),
+            &context,
+        ));
+        for (key, value) in storage {
+            account.storage.insert(
+                BytesKey::interpret_from(key.as_str(), &context),
+                BytesValue::interpret_from(value.as_str(), &context),
+            );
+        }
+
+        self.world.set_state_step(
+            SetStateStep::new().put_account(GAME_SC_ADDR.eval_to_expr().as_str(), account),
+        );
+
+        self
+    }
+
     fn create_game(
         &mut self,
         waiting_time: u64,
@@ -120,24 +240,112 @@ impl GameContractState {
                 number_of_players_min,
                 number_of_players_max,
                 wager,
+                OptionalValue::<u64>::None,
+                OptionalValue::<u64>::None,
             )
-            .egld_or_single_esdt(
-                &EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
-                0u64,
-                &BigUint::from(STARTING_FEE),
-            )
+            .egld_or_single_esdt(&self.token_id, 0u64, &BigUint::from(STARTING_FEE))
             .with_result(ExpectValue(expected_game_id))
             .run();
 
         self
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn create_game_with_payment(
+        &mut self,
+        waiting_time: u64,
+        number_of_players_min: u64,
+        number_of_players_max: u64,
+        wager: RustBigUint,
+        caller: TestAddress,
+        token_id: &EgldOrEsdtTokenIdentifier<StaticApi>,
+        start_fee: u64,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        let start_fee = BigUint::from(start_fee);
+        let tx = self
+            .world
+            .tx()
+            .from(caller)
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .create_game(
+                waiting_time,
+                number_of_players_min,
+                number_of_players_max,
+                wager,
+                OptionalValue::<u64>::None,
+                OptionalValue::<u64>::None,
+            )
+            .egld_or_single_esdt(token_id, 0u64, &start_fee);
+
+        match expected_error {
+            OptionalValue::Some(val) => {
+                tx.with_result(ExpectError(val.0, val.1)).run();
+            }
+            OptionalValue::None => {
+                tx.run();
+            }
+        }
+
+        self
+    }
+
+    fn create_scheduled_game(
+        &mut self,
+        waiting_time: u64,
+        join_deadline: u64,
+        start_time: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        let start_fee = BigUint::from(STARTING_FEE);
+        let tx = self
+            .world
+            .tx()
+            .from(caller)
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .create_game(
+                waiting_time,
+                2u64,
+                2u64,
+                100u64,
+                OptionalValue::Some(join_deadline),
+                OptionalValue::Some(start_time),
+            )
+            .egld_or_single_esdt(&self.token_id, 0u64, &start_fee);
+
+        match expected_error {
+            OptionalValue::Some(val) => {
+                tx.with_result(ExpectError(val.0, val.1)).run();
+            }
+            OptionalValue::None => {
+                tx.run();
+            }
+        }
+
+        self
+    }
+
     fn join_game(
         &mut self,
         game_id: u64,
         caller: TestAddress,
         amount: RustBigUint,
         expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        let token_id = self.token_id.clone();
+        self.join_game_with_token(game_id, caller, &token_id, amount, expected_error)
+    }
+
+    fn join_game_with_token(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        token_id: &EgldOrEsdtTokenIdentifier<StaticApi>,
+        amount: RustBigUint,
+        expected_error: OptionalValue<(u64, &str)>,
     ) -> &mut Self {
         match expected_error {
             OptionalValue::Some(val) => {
@@ -147,11 +355,7 @@ impl GameContractState {
                     .to(GAME_SC_ADDR)
                     .typed(game_proxy::MvxGameScProxy)
                     .join_game(game_id)
-                    .egld_or_single_esdt(
-                        &EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
-                        0u64,
-                        &BigUint::from(amount),
-                    )
+                    .egld_or_single_esdt(token_id, 0u64, &BigUint::from(amount))
                     .with_result(ExpectError(val.0, val.1))
                     .run();
             }
@@ -162,11 +366,7 @@ impl GameContractState {
                     .to(GAME_SC_ADDR)
                     .typed(game_proxy::MvxGameScProxy)
                     .join_game(game_id)
-                    .egld_or_single_esdt(
-                        &EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
-                        0u64,
-                        &BigUint::from(amount),
-                    )
+                    .egld_or_single_esdt(token_id, 0u64, &BigUint::from(amount))
                     .run();
             }
         }
@@ -205,6 +405,68 @@ impl GameContractState {
         self
     }
 
+    fn leave_game(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .leave_game(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .leave_game(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn cancel_game(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .cancel_game(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .cancel_game(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
     fn send_reward(
         &mut self,
         game_id: u64,
@@ -235,345 +497,4876 @@ impl GameContractState {
 
         self
     }
-}
 
-#[test]
-fn game_sc_deploy_test() {
-    let mut state = GameContractState::new();
+    fn submit_signed_result(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        signature: Vec<u8>,
+        winners: &[(TestAddress, u64)],
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        let winners = if winners.is_empty() {
+            OptionalValue::None
+        } else {
+            let mut val = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+            for (winner, percentage) in winners {
+                val.push((ManagedAddress::from(winner.eval_to_array()), *percentage));
+            }
+            OptionalValue::Some(val)
+        };
 
-    state.deploy();
-}
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .submit_signed_result(game_id, signature, winners)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .submit_signed_result(game_id, signature, winners)
+                    .run();
+            }
+        }
 
-#[test]
-fn game_sc_simple_game_flow() {
-    let mut state = GameContractState::new();
+        self
+    }
 
-    let waiting_time = 100u64;
-    let number_of_players_min = 1u64;
-    let number_of_players_max = 4u64;
-    let wager = RustBigUint::from(100u64);
+    fn emergency_withdraw(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .emergency_withdraw(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .emergency_withdraw(game_id)
+                    .run();
+            }
+        }
 
-    // deploy
-    state.deploy();
+        self
+    }
 
-    // check last game id before creation
-    state
-        .world
-        .query()
-        .to(GAME_SC_ADDR)
-        .typed(game_proxy::MvxGameScProxy)
-        .last_game_id()
-        .run();
+    fn dispute_result(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .dispute_result(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .dispute_result(game_id)
+                    .run();
+            }
+        }
 
-    // create first game
-    state.create_game(
-        waiting_time,
-        number_of_players_min,
-        number_of_players_max,
+        self
+    }
+
+    fn finalize_result(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .finalize_result(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .finalize_result(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn approve_settlement(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .approve_settlement(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .approve_settlement(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn execute_settlement(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .execute_settlement(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .execute_settlement(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn expire_settlement(
+        &mut self,
+        game_id: u64,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .expire_settlement(game_id)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .expire_settlement(game_id)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn continue_settlement(
+        &mut self,
+        game_id: u64,
+        batch_size: usize,
+        caller: TestAddress,
+        expected_error: OptionalValue<(u64, &str)>,
+    ) -> &mut Self {
+        match expected_error {
+            OptionalValue::Some(val) => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .continue_settlement(game_id, batch_size)
+                    .with_result(ExpectError(val.0, val.1))
+                    .run();
+            }
+            OptionalValue::None => {
+                self.world
+                    .tx()
+                    .from(caller)
+                    .to(GAME_SC_ADDR)
+                    .typed(game_proxy::MvxGameScProxy)
+                    .continue_settlement(game_id, batch_size)
+                    .run();
+            }
+        }
+
+        self
+    }
+
+    fn check_settlement_progress(&mut self, game_id: u64, expected_processed: u64) -> &mut Self {
+        let settlement = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .settlement(game_id)
+            .returns(ReturnsResult)
+            .run();
+
+        assert_eq!(settlement.processed, expected_processed);
+
+        self
+    }
+
+    fn send_tokens_to_sc(&mut self, amount: &str) -> &mut Self {
+        let transfer = TransferStep::new()
+            .from(OWNER_ADDR.eval_to_expr().as_str())
+            .to(GAME_SC_ADDR.eval_to_expr().as_str());
+
+        if self.token_id.is_egld() {
+            self.world.transfer_step(transfer.egld_value(amount));
+        } else {
+            self.world
+                .transfer_step(transfer.esdt_transfer(TOKEN_GAME_ID, 0, amount));
+        }
+
+        self
+    }
+
+    fn check_balance<A>(&mut self, address: A, expected_balance: RustBigUint) -> &mut Self
+    where
+        A: AnnotatedValue<ScenarioTxEnvData, ManagedAddress<StaticApi>>,
+    {
+        let expected_balance = BigUint::<StaticApi>::from(expected_balance);
+        if self.token_id.is_egld() {
+            self.world.check_account(address).balance(expected_balance);
+        } else {
+            self.world
+                .check_account(address)
+                .esdt_balance(self.token_id.clone().unwrap_esdt(), expected_balance);
+        }
+
+        self
+    }
+
+    fn set_admin(&mut self, user: TestAddress) -> &mut Self {
+        self.world
+            .tx()
+            .from(OWNER_ADDR)
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .set_admin(user)
+            .run();
+
+        self
+    }
+
+    fn check_game_status(
+        &mut self,
+        game_id: u64,
+        expected_status: game_proxy::Status,
+    ) -> &mut Self {
+        let status = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .game_status(game_id)
+            .returns(ReturnsResult)
+            .run();
+
+        assert_eq!(status, expected_status);
+
+        self
+    }
+
+    fn check_game_escrow(&mut self, game_id: u64, expected_escrow: u64) -> &mut Self {
+        self.world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .game_escrow(game_id)
+            .with_result(ExpectValue(expected_escrow))
+            .run();
+
+        self
+    }
+
+    fn check_open_games(
+        &mut self,
+        offset: usize,
+        size: usize,
+        filter: game_proxy::GameFilter<StaticApi>,
+        expected_game_ids: &[u64],
+    ) -> &mut Self {
+        let games = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_open_games(offset, size, filter)
+            .returns(ReturnsResult)
+            .run();
+
+        // the open games index is unordered
+        let mut game_ids: Vec<u64> = games.into_iter().map(|game| game.into_tuple().0).collect();
+        game_ids.sort();
+        assert_eq!(game_ids, expected_game_ids);
+
+        self
+    }
+
+    fn check_games(
+        &mut self,
+        from_game_id: u64,
+        size: usize,
+        filter: game_proxy::GameFilter<StaticApi>,
+        expected_game_ids: &[u64],
+    ) -> &mut Self {
+        let games = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_games(from_game_id, size, filter)
+            .returns(ReturnsResult)
+            .run();
+
+        let game_ids: Vec<u64> = games.into_iter().map(|game| game.into_tuple().0).collect();
+        assert_eq!(game_ids, expected_game_ids);
+
+        self
+    }
+
+    fn check_game_id_by_creator(
+        &mut self,
+        creator: TestAddress,
+        nonce: usize,
+        expected_game_id: u64,
+    ) -> &mut Self {
+        self.world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_game_id_by_creator(creator, nonce)
+            .with_result(ExpectValue(expected_game_id))
+            .run();
+
+        self
+    }
+
+    // checks only the given storage keys of the contract, an empty value means the key is not set
+    fn check_sc_storage(&mut self, storage: &[(&str, &str)]) -> &mut Self {
+        let mut check_account = CheckAccount::new();
+        check_account.storage = CheckStorage::Equal(CheckStorageDetails {
+            storages: storage
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        BytesKey::interpret_from(*key, &InterpreterContext::default()),
+                        CheckValue::Equal(BytesValue::interpret_from(
+                            *value,
+                            &InterpreterContext::default(),
+                        )),
+                    )
+                })
+                .collect(),
+            other_storages_allowed: true,
+        });
+
+        self.world.check_state_step(
+            CheckStateStep::new().put_account(GAME_SC_ADDR.eval_to_expr().as_str(), check_account),
+        );
+
+        self
+    }
+
+    fn check_proposal_approvers(&mut self, game_id: u64, expected_approvers: &[TestAddress]) {
+        let approvers: Vec<ManagedAddress<StaticApi>> = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_proposal_approvers(game_id)
+            .returns(ReturnsResult)
+            .run()
+            .into_iter()
+            .collect();
+
+        let expected_approvers: Vec<ManagedAddress<StaticApi>> = expected_approvers
+            .iter()
+            .map(|approver| ManagedAddress::from(approver.eval_to_array()))
+            .collect();
+        assert_eq!(approvers, expected_approvers);
+    }
+
+    fn set_operation_paused(&mut self, operation: game_proxy::Operation, paused: bool) {
+        if paused {
+            self.world
+                .tx()
+                .from(OWNER_ADDR)
+                .to(GAME_SC_ADDR)
+                .typed(game_proxy::MvxGameScProxy)
+                .pause_operation(operation)
+                .run();
+        } else {
+            self.world
+                .tx()
+                .from(OWNER_ADDR)
+                .to(GAME_SC_ADDR)
+                .typed(game_proxy::MvxGameScProxy)
+                .unpause_operation(operation)
+                .run();
+        }
+    }
+
+    // creation, joining, settlement, claims
+    fn check_pause_matrix(&mut self, expected_paused: [bool; 4]) {
+        let pause_matrix: Vec<(game_proxy::Operation, bool)> = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_pause_matrix()
+            .returns(ReturnsResult)
+            .run()
+            .into_iter()
+            .map(|val| val.into_tuple())
+            .collect();
+
+        assert_eq!(
+            pause_matrix,
+            vec![
+                (game_proxy::Operation::Creation, expected_paused[0]),
+                (game_proxy::Operation::Joining, expected_paused[1]),
+                (game_proxy::Operation::Settlement, expected_paused[2]),
+                (game_proxy::Operation::Claims, expected_paused[3]),
+            ]
+        );
+    }
+
+    fn check_roles(&mut self, user: TestAddress, expected_roles: Vec<game_proxy::Role>) {
+        let roles: Vec<game_proxy::Role> = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_roles(user)
+            .returns(ReturnsResult)
+            .run()
+            .into_iter()
+            .collect();
+
+        assert_eq!(roles, expected_roles);
+    }
+
+    fn get_pending_proposals(&mut self) -> Vec<u64> {
+        self.world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_pending_proposals()
+            .returns(ReturnsResult)
+            .run()
+            .into_iter()
+            .map(|proposal| proposal.game_id)
+            .collect()
+    }
+
+    fn get_claimable(
+        &mut self,
+        user: TestAddress,
+    ) -> Vec<(EgldOrEsdtTokenIdentifier<StaticApi>, RustBigUint)> {
+        self.world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .claimable(user)
+            .returns(ReturnsResult)
+            .run()
+            .into_iter()
+            .map(|claimable| {
+                let (token_id, amount) = claimable.into_tuple();
+                (
+                    token_id,
+                    RustBigUint::from_bytes_be(amount.to_bytes_be().as_slice()),
+                )
+            })
+            .collect()
+    }
+
+    // the contract should hold exactly the sum of all game escrows, the rake, the collected fees
+    // and the payouts not claimed yet
+    fn check_escrow_invariant(&mut self, game_ids: &[u64]) -> &mut Self {
+        let mut total_escrow = RustBigUint::from(0u64);
+        for game_id in game_ids {
+            let escrow = self
+                .world
+                .query()
+                .to(GAME_SC_ADDR)
+                .typed(game_proxy::MvxGameScProxy)
+                .game_escrow(*game_id)
+                .returns(ReturnsResultUnmanaged)
+                .run();
+            total_escrow += escrow;
+        }
+
+        let treasury = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .treasury(self.token_id.clone())
+            .returns(ReturnsResultUnmanaged)
+            .run();
+        let collected_fees = self
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .collected_fees(self.token_id.clone())
+            .returns(ReturnsResultUnmanaged)
+            .run();
+
+        let mut total_claimable = RustBigUint::from(0u64);
+        for user in [
+            USER1_ADDR, USER2_ADDR, USER3_ADDR, USER4_ADDR, USER5_ADDR, OWNER_ADDR,
+        ] {
+            for (token_id, amount) in self.get_claimable(user) {
+                if token_id == self.token_id {
+                    total_claimable += amount;
+                }
+            }
+        }
+
+        self.check_balance(
+            GAME_SC_ADDR,
+            total_escrow + treasury + collected_fees + total_claimable,
+        );
+
+        self
+    }
+}
+
+fn no_filter() -> game_proxy::GameFilter<StaticApi> {
+    game_proxy::GameFilter {
+        status: None,
+        wager_min: None,
+        wager_max: None,
+        creator: None,
+        token_id: None,
+    }
+}
+
+#[test]
+fn game_sc_deploy_test() {
+    let mut state = GameContractState::new();
+
+    state.deploy();
+}
+
+#[test]
+fn game_sc_simple_game_flow() {
+    simple_game_flow(GameContractState::new());
+}
+
+#[test]
+fn game_sc_simple_game_flow_egld() {
+    simple_game_flow(GameContractState::new_egld());
+}
+
+fn simple_game_flow(mut state: GameContractState) {
+    let waiting_time = 100u64;
+    let number_of_players_min = 1u64;
+    let number_of_players_max = 4u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+
+    // check last game id before creation
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .last_game_id()
+        .run();
+
+    // create first game
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1u64,
+    );
+
+    // check last game id, needs to be 1
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .last_game_id()
+        .with_result(ExpectValue(1u64))
+        .run();
+
+    // user1 tries to join the game, timestamp is ok, max players not reached, should work
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+
+    // min number of players reached, game should be valid
+    let game_setting = state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_settings(1u64)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    let game_settings = SingleValue::from(game_setting);
+    assert_eq!(game_settings.into().status, game_proxy::Status::Ready);
+
+    // user2 tries to join the game, shuld work
+    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+
+    // both users should be in players vec
+    let players = state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .players(1u64)
+        .returns(ReturnsResult)
+        .run();
+    let players_vec = players.to_vec();
+
+    assert!(players_vec.contains(&ManagedAddress::from(USER1_ADDR.eval_to_array())));
+    assert!(players_vec.contains(&ManagedAddress::from(USER2_ADDR.eval_to_array())));
+
+    // game should be in users'storage
+    let games_per_user1: MultiValueEncoded<StaticApi, u64> = state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .games_per_user(USER1_ADDR.eval_to_array())
+        .returns(ReturnsResult)
+        .run();
+    let games_per_user1_vec = games_per_user1.to_vec();
+
+    let games_per_user2: MultiValueEncoded<StaticApi, u64> = state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .games_per_user(USER2_ADDR.eval_to_array())
+        .returns(ReturnsResult)
+        .run();
+    let games_per_user2_vec = games_per_user2.to_vec();
+
+    assert!((games_per_user1_vec.contains(&1u64) && games_per_user2_vec.contains(&1u64)));
+}
+
+#[test]
+fn game_sc_complex_flow() {
+    complex_game_flow(GameContractState::new());
+}
+
+#[test]
+fn game_sc_complex_flow_egld() {
+    complex_game_flow(GameContractState::new_egld());
+}
+
+fn complex_game_flow(mut state: GameContractState) {
+    // game settings
+    let waiting_time = 100u64; // => timestamp 102 should be out of waiting time
+    let number_of_players_min = 2u64;
+    let number_of_players_max = 4u64;
+    let wager = RustBigUint::from(100u64);
+    let diff_amount = RustBigUint::from(5u64);
+
+    // deploy
+    state.deploy();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // create first game
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1,
+    );
+
+    // user1 joins
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+
+    let game_setting = state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_settings(1u64)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    let game_settings = SingleValue::from(game_setting);
+
+    assert_eq!(game_settings.into().status, game_proxy::Status::Open);
+
+    // user1 tries to claim back wager, should fail (waiting time not passed)
+    state.claim_back_wager(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "waiting time is not over yet")),
+    );
+
+    // user2 joins
+    state.join_game(
+        1u64,
+        USER2_ADDR,
+        diff_amount,
+        OptionalValue::Some((4, "wrong amount paid")),
+    ); // wrong amount paid
+
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(
+        1u64,
+        USER2_ADDR,
+        wager.clone(),
+        OptionalValue::Some((4, "user already joined this game")),
+    ); // user already joined
+
+    let game_setting = state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_settings(1u64)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    let game_settings = SingleValue::from(game_setting);
+
+    assert_eq!(game_settings.into().status, game_proxy::Status::Ready);
+
+    // user3 joins
+    state.join_game(1u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+
+    // set timestamp after time limit
+    state.world.current_block().block_timestamp(102u64);
+
+    // user4 joins, time has passed, fails
+    state.join_game(
+        1u64,
+        USER4_ADDR,
+        wager,
+        OptionalValue::Some((4, "waiting time has passed")),
+    );
+
+    // user4 tries to claim back wager, fails
+    state.claim_back_wager(
+        1u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "caller has not joined the game")),
+    );
+
+    // user1 tries to claim back wager, fails
+    state.claim_back_wager(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "can manually claim back wager only if the game is open")),
+    );
+
+    // send tokens to sc
+    state.send_tokens_to_sc("10_000");
+
+    state.check_balance(GAME_SC_ADDR, RustBigUint::from(10_320u64));
+
+    // owner sends rewards
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 8000u64)); // 80%
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 2000u64)); // 20%
+
+    // make owner admin first
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_admin(OWNER_ADDR)
+        .run();
+
+    // send reward
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+
+    // user1 should receive 80% of the reward
+    // reward = 3 * wager = 300 => user1's reward = 240
+    state.check_balance(USER1_ADDR, RustBigUint::from(100000140u64));
+
+    // user2's reward = 60
+    state.check_balance(USER2_ADDR, RustBigUint::from(99999960u64)); // balance - wager + 60
+}
+
+#[test]
+fn invalid_game_test() {
+    invalid_game_flow(GameContractState::new());
+}
+
+#[test]
+fn invalid_game_egld_test() {
+    invalid_game_flow(GameContractState::new_egld());
+}
+
+fn invalid_game_flow(mut state: GameContractState) {
+    // game settings
+    let waiting_time = 100u64; // => timestamp 102 should be out of waiting time
+    let number_of_players_min = 3u64;
+    let number_of_players_max = 5u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1);
+
+    // create game
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1,
+    );
+
+    // user1 joins
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+
+    // user2 joins
+    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+
+    // game is still invalid, min number of players not reached
+    let game_setting = state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_settings(1u64)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    let game_settings = SingleValue::from(game_setting);
+
+    assert_eq!(game_settings.into().status, game_proxy::Status::Open);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102);
+
+    // make owner admin first
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_admin(OWNER_ADDR)
+        .run();
+
+    // send reward, invalid game => players should receive back wager, creator should receive the creation fee back
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+
+    state.check_balance(USER1_ADDR, RustBigUint::from(100000000u64));
+
+    state.check_balance(USER2_ADDR, RustBigUint::from(100000000u64));
+
+    state.check_balance(OWNER_ADDR, RustBigUint::from(100000000u64));
+
+    // game is refunded, it can't be refunded twice
+    state.check_game_status(1u64, game_proxy::Status::Refunded);
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game already refunded")),
+    );
+
+    // players were already refunded, they can't claim back the wager
+    state.claim_back_wager(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "can manually claim back wager only if the game is open")),
+    );
+}
+
+#[test]
+fn full_game_lifecycle_test() {
+    let mut state = GameContractState::new();
+
+    // game settings
+    let waiting_time = 100u64;
+    let number_of_players_min = 2u64;
+    let number_of_players_max = 3u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // create game, no players yet => open
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1,
+    );
+    state.check_game_status(1u64, game_proxy::Status::Open);
+
+    // user1 joins, min number of players not reached => still open
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Open);
+
+    // user2 joins, min number of players reached => ready
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Ready);
+
+    // ready game can't be settled before the waiting time is over
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "waiting time is not over yet")),
+    );
+
+    // user3 joins, max number of players reached => in progress
+    state.join_game(1u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+
+    // game is full, no one else can join
+    state.join_game(
+        1u64,
+        USER4_ADDR,
+        wager,
+        OptionalValue::Some((4, "max number of players reached")),
+    );
+
+    // full game can be settled before the waiting time is over
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64)); // 100%
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+
+    // reward = 3 * wager = 300
+    state.check_balance(USER1_ADDR, RustBigUint::from(100000200u64));
+
+    // settled game can't be rewarded twice
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "game already settled")),
+    );
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    // players of a settled game can't claim back the wager
+    state.claim_back_wager(
+        1u64,
+        USER2_ADDR,
+        OptionalValue::Some((4, "can manually claim back wager only if the game is open")),
+    );
+}
+
+#[test]
+fn ready_game_starts_after_waiting_time_test() {
+    let mut state = GameContractState::new();
+
+    // game settings
+    let waiting_time = 100u64;
+    let number_of_players_min = 1u64;
+    let number_of_players_max = 4u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1,
+    );
+
+    // user1 joins => ready
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Ready);
+
+    // set now = 102, past waiting time => in progress
+    state.world.current_block().block_timestamp(102u64);
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+
+    // game started, no one else can join
+    state.join_game(
+        1u64,
+        USER2_ADDR,
+        wager,
+        OptionalValue::Some((4, "waiting time has passed")),
+    );
+
+    // tie/draw => wagers sent back, game settled
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+
+    state.check_balance(USER1_ADDR, RustBigUint::from(100000000u64));
+
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game already settled")),
+    );
+}
+
+#[test]
+fn send_reward_payout_validation_test() {
+    let mut state = GameContractState::new();
+
+    // game settings
+    let waiting_time = 100u64;
+    let number_of_players_min = 2u64;
+    let number_of_players_max = 4u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(
+        waiting_time,
+        number_of_players_min,
+        number_of_players_max,
+        wager.clone(),
+        OWNER_ADDR,
+        1,
+    );
+
+    // 3 players => prize pool = 300
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER3_ADDR, wager, OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    let user1 = ManagedAddress::<StaticApi>::from(USER1_ADDR.eval_to_array());
+    let user2 = ManagedAddress::<StaticApi>::from(USER2_ADDR.eval_to_array());
+    let user3 = ManagedAddress::<StaticApi>::from(USER3_ADDR.eval_to_array());
+    let user4 = ManagedAddress::<StaticApi>::from(USER4_ADDR.eval_to_array());
+
+    // 150% => fails
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1.clone(), 10_000u64));
+    winners.push((user2.clone(), 5_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "winners percentages must add up to 100%")),
+    );
+
+    // 40% => fails
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1.clone(), 4_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "winners percentages must add up to 100%")),
+    );
+
+    // winner with 0% => fails
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1.clone(), 10_000u64));
+    winners.push((user2.clone(), 0u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "invalid winner percentage")),
+    );
+
+    // user4 did not join the game => fails
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1.clone(), 5_000u64));
+    winners.push((user4, 5_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "winner has not joined the game")),
+    );
+
+    // duplicate winner => fails
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1.clone(), 5_000u64));
+    winners.push((user1.clone(), 5_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "duplicate winner")),
+    );
+
+    // 33.33% + 33.33% + 33.34% => 99 + 99 + 100, the dust (2) goes to the first winner
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((user1, 3_333u64));
+    winners.push((user2, 3_333u64));
+    winners.push((user3, 3_334u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+
+    state.check_balance(USER1_ADDR, RustBigUint::from(100000001u64));
+    state.check_balance(USER2_ADDR, RustBigUint::from(99999999u64));
+    state.check_balance(USER3_ADDR, RustBigUint::from(100000000u64));
+
+    // the whole prize pool was paid out, only the start fee is left
+    state.check_balance(GAME_SC_ADDR, RustBigUint::from(STARTING_FEE));
+}
+
+#[test]
+fn game_escrow_invariant_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let first_wager = RustBigUint::from(100u64);
+    let second_wager = RustBigUint::from(50u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // both games hold only the start fee
+    state.create_game(waiting_time, 2u64, 3u64, first_wager.clone(), OWNER_ADDR, 1);
+    state.create_game(
+        waiting_time,
+        3u64,
+        5u64,
+        second_wager.clone(),
+        USER5_ADDR,
+        2,
+    );
+    state.check_game_escrow(1u64, STARTING_FEE);
+    state.check_game_escrow(2u64, STARTING_FEE);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    // wagers are credited to their own game
+    state.join_game(1u64, USER1_ADDR, first_wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, first_wager, OptionalValue::None);
+    state.join_game(2u64, USER1_ADDR, second_wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER3_ADDR, second_wager, OptionalValue::None);
+    state.check_game_escrow(1u64, 220u64);
+    state.check_game_escrow(2u64, 120u64);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    // second game did not get enough players, user3 claims back the wager
+    state.claim_back_wager(2u64, USER3_ADDR, OptionalValue::None);
+    state.check_game_escrow(1u64, 220u64);
+    state.check_game_escrow(2u64, 70u64);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    // first game is settled, the start fee is moved to the collected fees
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.check_game_escrow(1u64, 0u64);
+    state.check_game_escrow(2u64, 70u64);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    // second game is refunded, its escrow is emptied
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_escrow(2u64, 0u64);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE));
+}
+
+#[test]
+fn multi_token_games_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+    let other_wager = RustBigUint::from(50u64);
+    let other_start_fee = 10u64;
+    let other_token = EgldOrEsdtTokenIdentifier::<StaticApi>::esdt(TOKEN_OTHER_ID);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // accept a second token, with its own min wager and start fee
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .add_accepted_token(other_token.clone())
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_min_wager(other_token.clone(), other_wager.clone())
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_start_fee(other_token.clone(), other_start_fee)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 uses the main token
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 1);
+
+    // wager below the token's min wager => fails
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        RustBigUint::from(40u64),
+        USER5_ADDR,
+        &other_token,
+        other_start_fee,
+        OptionalValue::Some((4, "wager too low")),
+    );
+
+    // general start fee instead of the token's start fee => fails
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        other_wager.clone(),
+        USER5_ADDR,
+        &other_token,
+        STARTING_FEE,
+        OptionalValue::Some((4, "start game payment amount not right")),
+    );
+
+    // token not accepted => fails
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        other_wager.clone(),
+        USER5_ADDR,
+        &EgldOrEsdtTokenIdentifier::egld(),
+        other_start_fee,
+        OptionalValue::Some((4, "wrong token id")),
+    );
+
+    // game 2 uses the second token
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        other_wager.clone(),
+        USER5_ADDR,
+        &other_token,
+        other_start_fee,
+        OptionalValue::None,
+    );
+
+    let game_settings = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_settings(2u64)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(game_settings.token_id, other_token);
+
+    // wager paid in another token than the game's => fails
+    state.join_game(
+        2u64,
+        USER1_ADDR,
+        other_wager.clone(),
+        OptionalValue::Some((4, "wrong token sent")),
+    );
+
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game_with_token(
+        2u64,
+        USER1_ADDR,
+        &other_token,
+        other_wager.clone(),
+        OptionalValue::None,
+    );
+    state.join_game_with_token(
+        2u64,
+        USER2_ADDR,
+        &other_token,
+        other_wager,
+        OptionalValue::None,
+    );
+
+    // main token replaced while the games are running
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_id(EgldOrEsdtTokenIdentifier::egld())
+        .run();
+
+    // the old main token is not accepted anymore
+    let game_token = state.token_id.clone();
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        wager,
+        OWNER_ADDR,
+        &game_token,
+        STARTING_FEE,
+        OptionalValue::Some((4, "wrong token id")),
+    );
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    // game 1 did not get enough players, refunds are still paid in its own token
+    state.claim_back_wager(1u64, USER1_ADDR, OptionalValue::None);
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+    state
+        .world
+        .check_account(USER1_ADDR)
+        .esdt_balance(TOKEN_GAME, BALANCE)
+        .balance(BALANCE);
+    state
+        .world
+        .check_account(OWNER_ADDR)
+        .esdt_balance(TOKEN_GAME, BALANCE)
+        .balance(BALANCE);
+
+    // game 2 rewards are paid in its own token
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(2u64, OptionalValue::Some(winners), OptionalValue::None);
+    state
+        .world
+        .check_account(USER2_ADDR)
+        .esdt_balance(TOKEN_OTHER, BALANCE + 50u64);
+    state
+        .world
+        .check_account(USER1_ADDR)
+        .esdt_balance(TOKEN_OTHER, BALANCE - 50u64);
+    state.check_game_escrow(2u64, 0u64);
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .collected_fees(other_token)
+        .with_result(ExpectValue(other_start_fee))
+        .run();
+}
+
+#[test]
+fn events_test() {
+    let mut state = GameContractState::new();
+
+    let wager = RustBigUint::from(100u64);
+    let owner = OWNER_ADDR.eval_to_array().to_vec();
+    let user1 = USER1_ADDR.eval_to_array().to_vec();
+    let user2 = USER2_ADDR.eval_to_array().to_vec();
+    let user3 = USER3_ADDR.eval_to_array().to_vec();
+    let game_token = TOKEN_GAME_ID.to_vec();
+
+    // deploy
+    state.deploy();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_admin(OWNER_ADDR)
+        .with_result(ExpectLogs(vec![event_log(
+            "setAdmin",
+            vec![owner.clone()],
+            None,
+        )]))
+        .run();
+
+    // game 1 => created, ready after the first player, in progress after the second
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .create_game(
+            100u64,
+            1u64,
+            2u64,
+            wager.clone(),
+            OptionalValue::<u64>::None,
+            OptionalValue::<u64>::None,
+        )
+        .egld_or_single_esdt(&state.token_id, 0u64, &BigUint::from(STARTING_FEE))
+        .with_result(ExpectLogs(vec![event_log(
+            "createGame",
+            vec![vec![1u8], owner.clone()],
+            None,
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .join_game(1u64)
+        .egld_or_single_esdt(&state.token_id, 0u64, &BigUint::from(wager.clone()))
+        .with_result(ExpectLogs(vec![
+            event_log(
+                "joinGame",
+                vec![vec![1u8], user1.clone()],
+                Some(vec![100u8]),
+            ),
+            event_log("gameStatus", vec![vec![1u8], vec![1u8]], None),
+        ]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .join_game(1u64)
+        .egld_or_single_esdt(&state.token_id, 0u64, &BigUint::from(wager.clone()))
+        .with_result(ExpectLogs(vec![
+            event_log(
+                "joinGame",
+                vec![vec![1u8], user2.clone()],
+                Some(vec![100u8]),
+            ),
+            event_log("gameStatus", vec![vec![1u8], vec![2u8]], None),
+        ]))
+        .run();
+
+    // reward paid to the winner => settled
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .send_reward(1u64, OptionalValue::Some(winners))
+        .with_result(ExpectLogs(vec![
+            transfer_log(),
+            event_log(
+                "rewardPaid",
+                vec![vec![1u8], user1.clone(), game_token.clone()],
+                Some(vec![200u8]),
+            ),
+            event_log(
+                "collectFees",
+                vec![vec![1u8], game_token.clone()],
+                Some(vec![STARTING_FEE as u8]),
+            ),
+            event_log("gameStatus", vec![vec![1u8], vec![3u8]], None),
+        ]))
+        .run();
+
+    // game 2 => not enough players, wager claimed back, refunded
+    state.create_game(100u64, 3u64, 3u64, wager.clone(), OWNER_ADDR, 2u64);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER2_ADDR, wager, OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    state
+        .world
+        .tx()
+        .from(USER3_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .claim_back_wager(2u64)
+        .with_result(ExpectLogs(vec![
+            transfer_log(),
+            event_log("claimBackWager", vec![vec![2u8], user3], Some(vec![100u8])),
+        ]))
+        .run();
+
+    // wager returned to user2, start fee returned to the creator
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .send_reward(
+            2u64,
+            OptionalValue::<MultiValueEncoded<StaticApi, (ManagedAddress<StaticApi>, u64)>>::None,
+        )
+        .with_result(ExpectLogs(vec![
+            transfer_log(),
+            transfer_log(),
+            event_log("gameStatus", vec![vec![2u8], vec![5u8]], None),
+        ]))
+        .run();
+
+    // owner config changes
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .with_result(ExpectLogs(vec![event_log("disableSC", vec![], None)]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .enable_sc()
+        .with_result(ExpectLogs(vec![event_log("enableSC", vec![], None)]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_id(EgldOrEsdtTokenIdentifier::esdt(TOKEN_OTHER_ID))
+        .with_result(ExpectLogs(vec![event_log(
+            "setTokenId",
+            vec![TOKEN_OTHER_ID.to_vec()],
+            None,
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_game_start_fee(30u64)
+        .with_result(ExpectLogs(vec![event_log(
+            "setGameStartFee",
+            vec![],
+            Some(vec![30u8]),
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .add_accepted_token(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectLogs(vec![event_log(
+            "addAcceptedToken",
+            vec![game_token.clone()],
+            None,
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_min_wager(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID), 10u64)
+        .with_result(ExpectLogs(vec![event_log(
+            "setTokenMinWager",
+            vec![game_token.clone()],
+            Some(vec![10u8]),
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_start_fee(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID), 5u64)
+        .with_result(ExpectLogs(vec![event_log(
+            "setTokenStartFee",
+            vec![game_token.clone()],
+            Some(vec![5u8]),
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .remove_accepted_token(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectLogs(vec![event_log(
+            "removeAcceptedToken",
+            vec![game_token],
+            None,
+        )]))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .remove_admin(OWNER_ADDR)
+        .with_result(ExpectLogs(vec![event_log(
+            "removeAdmin",
+            vec![owner],
+            None,
+        )]))
+        .run();
+}
+
+#[test]
+fn rake_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // 100% rake => fails
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(10_000u64)
+        .with_result(ExpectError(4, "rake must be lower than 100%"))
+        .run();
+
+    // only the owner can set the rake
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(500u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // 5% rake
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(500u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+
+    // the rake is fixed when the game is created
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(9_000u64)
+        .run();
+
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER4_ADDR, wager, OptionalValue::None);
+
+    // prize pool = 200, rake = 10 => user1 gets 190
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 90u64));
+    state.check_game_escrow(1u64, 0u64);
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .treasury(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectValue(10u64))
+        .run();
+
+    // tie/draw => no rake, the wagers are returned in full
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+
+    // only the owner can withdraw the rake
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // can't withdraw more than the accrued rake
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::Some(BigUint::from(11u64)),
+        )
+        .with_result(ExpectError(4, "amount exceeds available balance"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .run();
+
+    // owner paid 2 start fees, got the rake back
+    state.check_balance(
+        OWNER_ADDR,
+        RustBigUint::from(BALANCE - 2 * STARTING_FEE + 10u64),
+    );
+
+    // the start fees are left in the collected fees
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "nothing to withdraw"))
+        .run();
+}
+
+#[test]
+fn withdraw_fees_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => valid, game 2 => not enough players, game 3 => still open
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 1);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 2);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 3);
+    state.join_game(3u64, USER4_ADDR, wager, OptionalValue::None);
+
+    // nothing collected before a game is settled
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "nothing to withdraw"))
+        .run();
+
+    // only the settled game's start fee is collected, the refunded one goes back to the creator
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - 2 * STARTING_FEE));
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .collected_fees(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectValue(STARTING_FEE))
+        .run();
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64]);
+
+    // only the owner can withdraw the fees
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // the open game's escrow can't be withdrawn
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::Some(BigUint::from(STARTING_FEE + 1)),
+        )
+        .with_result(ExpectError(4, "amount exceeds available balance"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::Some(BigUint::from(5u64)),
+        )
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .run();
+
+    state.check_balance(OWNER_ADDR, RustBigUint::from(BALANCE + STARTING_FEE));
+    state.check_game_escrow(3u64, STARTING_FEE + 100u64);
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64]);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_fees(
+            EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            OptionalValue::<BigUint<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "nothing to withdraw"))
+        .run();
+}
+
+#[test]
+fn leave_game_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // 100% leave penalty => fails
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_leave_penalty(10_000u64)
+        .with_result(ExpectError(4, "leave penalty must be lower than 100%"))
+        .run();
+
+    // only the owner can set the leave penalty
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_leave_penalty(1_000u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // 10% leave penalty
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_leave_penalty(1_000u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Ready);
+
+    // user3 has not joined => fails
+    state.leave_game(
+        1u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "caller has not joined the game")),
+    );
+
+    // user2 leaves, 10 is kept as a fee and the game drops back below its minimum
+    state.leave_game(1u64, USER2_ADDR, OptionalValue::None);
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE - 10u64));
+    state.check_game_status(1u64, game_proxy::Status::Open);
+    state.check_game_escrow(1u64, STARTING_FEE + 100u64);
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .collected_fees(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectValue(10u64))
+        .run();
+
+    // user2 can join again, the game is full once user3 joins
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+
+    // the game has started => fails
+    state.leave_game(
+        1u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "game is not open")),
+    );
+
+    // game 2 => only one player
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER4_ADDR, wager, OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    // too late to leave, the wager can be claimed back in full
+    state.leave_game(
+        2u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "waiting time has passed")),
+    );
+    state.claim_back_wager(2u64, USER4_ADDR, OptionalValue::None);
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+
+    state.check_escrow_invariant(&[1u64, 2u64]);
+}
+
+#[test]
+fn cancel_game_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => created by user5, ready
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), USER5_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+
+    // a player can't cancel the game
+    state.cancel_game(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "only the creator or an admin can cancel the game")),
+    );
+
+    // the creator cancels, wagers and start fee are returned
+    state.cancel_game(1u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Cancelled);
+    state.check_game_escrow(1u64, 0u64);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE));
+
+    // the refunded players are removed from the game and from their games
+    state.check_sc_storage(&[
+        ("str:players|u64:1|str:.len", ""),
+        ("str:players|u64:1|str:.index|address:user1", ""),
+        ("str:players|u64:1|str:.index|address:user2", ""),
+        ("str:gamesPerUser|address:user1|str:.len", ""),
+        ("str:gamesPerUser|address:user2|str:.len", ""),
+    ]);
+
+    // cancelled is a terminal state
+    state.cancel_game(
+        1u64,
+        USER5_ADDR,
+        OptionalValue::Some((4, "game is not open")),
+    );
+    state.join_game(
+        1u64,
+        USER3_ADDR,
+        wager.clone(),
+        OptionalValue::Some((4, "game is not open")),
+    );
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game was cancelled")),
+    );
+
+    // only the owner can set the cancel policy
+    state
+        .world
+        .tx()
+        .from(USER5_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_forfeit_start_fee_on_cancel(true)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_forfeit_start_fee_on_cancel(true)
+        .run();
+
+    // game 2 => cancelled by an admin, the start fee is forfeited
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), USER5_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.cancel_game(2u64, OWNER_ADDR, OptionalValue::None);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - STARTING_FEE));
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .collected_fees(EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID))
+        .with_result(ExpectValue(STARTING_FEE))
+        .run();
+
+    // game 3 => full, it can't be cancelled anymore
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 3);
+    state.join_game(3u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(3u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.cancel_game(
+        3u64,
+        USER5_ADDR,
+        OptionalValue::Some((4, "game is not open")),
+    );
+
+    // game 4 => the wagers are refunded in batches, like a settlement
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_batch_size(2usize)
+        .run();
+
+    state.create_game(waiting_time, 2u64, 5u64, wager.clone(), USER5_ADDR, 4);
+    for user in [USER1_ADDR, USER2_ADDR, USER3_ADDR, USER4_ADDR] {
+        state.join_game(4u64, user, wager.clone(), OptionalValue::None);
+    }
+    state.cancel_game(4u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(4u64, game_proxy::Status::Settling);
+    state.check_settlement_progress(4u64, 2u64);
+
+    // the creator finishes it, even during maintenance, but not if the claims are paused
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .run();
+    state.set_operation_paused(game_proxy::Operation::Claims, true);
+    state.continue_settlement(
+        4u64,
+        2usize,
+        USER5_ADDR,
+        OptionalValue::Some((4, "operation paused")),
+    );
+    state.set_operation_paused(game_proxy::Operation::Claims, false);
+
+    state.continue_settlement(4u64, 2usize, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(4u64, game_proxy::Status::Cancelled);
+    state.check_game_escrow(4u64, 0u64);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - 3 * STARTING_FEE));
+    state.check_sc_storage(&[
+        ("str:players|u64:4|str:.len", ""),
+        ("str:gamesPerUser|address:user3|str:.len", ""),
+        ("str:gamesPerUser|address:user4|str:.len", ""),
+    ]);
+
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64, 4u64]);
+}
+
+#[test]
+fn game_discovery_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let other_token = EgldOrEsdtTokenIdentifier::<StaticApi>::esdt(TOKEN_OTHER_ID);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .add_accepted_token(other_token.clone())
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => full and settled
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(100u64),
+        OWNER_ADDR,
+        1,
+    );
+    state.join_game(
+        1u64,
+        USER1_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.join_game(
+        1u64,
+        USER2_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+
+    // game 2 => open, game 3 => ready
+    state.create_game(
+        waiting_time,
+        2u64,
+        3u64,
+        RustBigUint::from(50u64),
+        USER5_ADDR,
+        2,
+    );
+    state.join_game(
+        2u64,
+        USER1_ADDR,
+        RustBigUint::from(50u64),
+        OptionalValue::None,
+    );
+    state.create_game(
+        waiting_time,
+        1u64,
+        3u64,
+        RustBigUint::from(200u64),
+        USER5_ADDR,
+        3,
+    );
+    state.join_game(
+        3u64,
+        USER2_ADDR,
+        RustBigUint::from(200u64),
+        OptionalValue::None,
+    );
+
+    // game 4 => open, in the other token
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        3u64,
+        RustBigUint::from(100u64),
+        USER4_ADDR,
+        &other_token,
+        STARTING_FEE,
+        OptionalValue::None,
+    );
+
+    // game 5 => cancelled
+    state.create_game(
+        waiting_time,
+        2u64,
+        3u64,
+        RustBigUint::from(100u64),
+        USER5_ADDR,
+        5,
+    );
+    state.cancel_game(5u64, USER5_ADDR, OptionalValue::None);
+
+    // only the games accepting players are indexed
+    let mut open_game_ids: Vec<u64> = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .open_games()
+        .returns(ReturnsResultUnmanaged)
+        .run()
+        .into_vec();
+    open_game_ids.sort();
+    assert_eq!(open_game_ids, vec![2u64, 3u64, 4u64]);
+
+    state.check_open_games(0, 100, no_filter(), &[2u64, 3u64, 4u64]);
+    state.check_open_games(
+        0,
+        100,
+        game_proxy::GameFilter {
+            status: Some(game_proxy::Status::Ready),
+            ..no_filter()
+        },
+        &[3u64],
+    );
+    state.check_open_games(
+        0,
+        100,
+        game_proxy::GameFilter {
+            creator: Some(ManagedAddress::from(USER5_ADDR.eval_to_array())),
+            ..no_filter()
+        },
+        &[2u64, 3u64],
+    );
+    state.check_open_games(
+        0,
+        100,
+        game_proxy::GameFilter {
+            token_id: Some(other_token),
+            ..no_filter()
+        },
+        &[4u64],
+    );
+    state.check_open_games(
+        0,
+        100,
+        game_proxy::GameFilter {
+            wager_min: Some(BigUint::from(60u64)),
+            wager_max: Some(BigUint::from(150u64)),
+            ..no_filter()
+        },
+        &[4u64],
+    );
+
+    // pages of the open games index
+    let first_page = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_open_games(0usize, 2usize, no_filter())
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(first_page.len(), 2);
+    let second_page = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_open_games(2usize, 2usize, no_filter())
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(second_page.len(), 1);
+
+    // all games, in ascending order
+    state.check_games(1, 100, no_filter(), &[1u64, 2u64, 3u64, 4u64, 5u64]);
+    state.check_games(2, 2, no_filter(), &[2u64, 3u64]);
+    state.check_games(
+        1,
+        100,
+        game_proxy::GameFilter {
+            status: Some(game_proxy::Status::Settled),
+            ..no_filter()
+        },
+        &[1u64],
+    );
+    state.check_games(
+        1,
+        100,
+        game_proxy::GameFilter {
+            status: Some(game_proxy::Status::Cancelled),
+            ..no_filter()
+        },
+        &[5u64],
+    );
+    state.check_games(
+        1,
+        100,
+        game_proxy::GameFilter {
+            creator: Some(ManagedAddress::from(USER5_ADDR.eval_to_array())),
+            ..no_filter()
+        },
+        &[2u64, 3u64, 5u64],
+    );
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    // no game accepts players anymore, the ready game has started
+    state.check_open_games(0, 100, no_filter(), &[]);
+    state.check_games(
+        1,
+        100,
+        game_proxy::GameFilter {
+            status: Some(game_proxy::Status::InProgress),
+            ..no_filter()
+        },
+        &[3u64],
+    );
+    state.check_games(
+        1,
+        100,
+        game_proxy::GameFilter {
+            status: Some(game_proxy::Status::Open),
+            ..no_filter()
+        },
+        &[2u64, 4u64],
+    );
+
+    // an expired game leaves the index once its wagers are claimed back
+    state.claim_back_wager(2u64, USER1_ADDR, OptionalValue::None);
+    let mut open_game_ids: Vec<u64> = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .open_games()
+        .returns(ReturnsResultUnmanaged)
+        .run()
+        .into_vec();
+    open_game_ids.sort();
+    assert_eq!(open_game_ids, vec![3u64, 4u64]);
+}
+
+#[test]
+fn game_info_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+
+    let game_info = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_game_info(1u64)
+        .returns(ReturnsResult)
+        .run();
+
+    assert_eq!(game_info.game_id, 1u64);
+    assert_eq!(
+        game_info.game_settings.creator,
+        ManagedAddress::from(OWNER_ADDR.eval_to_array())
+    );
+    assert_eq!(game_info.player_count, 2u64);
+    assert!(game_info
+        .players
+        .contains(&ManagedAddress::from(USER1_ADDR.eval_to_array())));
+    assert!(game_info
+        .players
+        .contains(&ManagedAddress::from(USER2_ADDR.eval_to_array())));
+    assert_eq!(game_info.pool, BigUint::from(STARTING_FEE + 200u64));
+    assert_eq!(game_info.time_remaining, 100u64);
+    assert_eq!(game_info.status, game_proxy::Status::Ready);
+
+    // set now = 102, past waiting time => the ready game has started
+    state.world.current_block().block_timestamp(102u64);
+
+    let game_info = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_game_info(1u64)
+        .returns(ReturnsResult)
+        .run();
+
+    assert_eq!(game_info.time_remaining, 0u64);
+    assert_eq!(game_info.status, game_proxy::Status::InProgress);
+    assert_eq!(game_info.game_settings.status, game_proxy::Status::Ready);
+
+    // batch
+    state.create_game(waiting_time, 2u64, 3u64, wager, USER5_ADDR, 2);
+
+    let mut game_ids = MultiValueEncoded::<StaticApi, u64>::new();
+    game_ids.push(2u64);
+    game_ids.push(1u64);
+    let games_info = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_games_info(game_ids)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    assert_eq!(games_info.len(), 2);
+    assert_eq!(games_info[0].game_id, 2u64);
+    assert_eq!(games_info[0].player_count, 0u64);
+    assert_eq!(games_info[0].pool, BigUint::from(STARTING_FEE));
+    assert_eq!(games_info[0].status, game_proxy::Status::Open);
+    assert_eq!(games_info[1].game_id, 1u64);
+
+    // unknown game => fails
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_game_info(3u64)
+        .with_result(ExpectError(4, "no settings for game id"))
+        .run();
+}
+
+#[test]
+fn game_id_by_creator_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+    let user5 = ManagedAddress::<StaticApi>::from(USER5_ADDR.eval_to_array());
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // games with identical settings get their own entries
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), USER5_ADDR, 1);
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), USER5_ADDR, 2);
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 3);
+
+    state.check_game_id_by_creator(USER5_ADDR, 1, 1u64);
+    state.check_game_id_by_creator(USER5_ADDR, 2, 2u64);
+    state.check_game_id_by_creator(OWNER_ADDR, 1, 3u64);
+
+    // the lookups don't depend on the status of the game
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Ready);
+    state.check_game_id_by_creator(USER5_ADDR, 1, 1u64);
+    state.check_game_id_by_creator(USER5_ADDR, 2, 2u64);
+    state.check_game_id_by_creator(OWNER_ADDR, 1, 3u64);
+
+    state.join_game(1u64, USER3_ADDR, wager, OptionalValue::None);
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.check_game_id_by_creator(USER5_ADDR, 1, 1u64);
+    state.check_game_id_by_creator(USER5_ADDR, 2, 2u64);
+    state.check_game_id_by_creator(OWNER_ADDR, 1, 3u64);
+
+    // unknown nonce => fails
+    for nonce in [0usize, 3usize] {
+        state
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .get_game_id_by_creator(user5.clone(), nonce)
+            .with_result(ExpectError(4, "no game for creator nonce"))
+            .run();
+    }
+
+    // upgrading a deployment with the current layout leaves the index as is
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .upgrade()
+        .code(GAME_SC_PATH)
+        .run();
+    state.check_game_id_by_creator(USER5_ADDR, 1, 1u64);
+    state.check_game_id_by_creator(USER5_ADDR, 2, 2u64);
+    state.check_game_id_by_creator(OWNER_ADDR, 1, 3u64);
+
+    let game_ids = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .games_by_creator(user5)
+        .returns(ReturnsResultUnmanaged)
+        .run()
+        .into_vec();
+    assert_eq!(game_ids, vec![1u64, 2u64]);
+}
+
+#[test]
+fn settled_game_archive_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // 10% rake
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(1_000u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+
+    state.check_sc_storage(&[
+        ("str:players|u64:1|str:.len", "2"),
+        ("str:gamesPerUser|address:user1|str:.len", "1"),
+        ("str:gamesPerUser|address:user2|str:.len", "1"),
+        ("str:gameEscrow|u64:1", "220"),
+    ]);
+
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+
+    // the settings, players and escrow are removed
+    state.check_sc_storage(&[
+        ("str:gameSettings|u64:1", ""),
+        ("str:players|u64:1|str:.len", ""),
+        ("str:players|u64:1|str:.item|u32:1", ""),
+        ("str:players|u64:1|str:.item|u32:2", ""),
+        ("str:players|u64:1|str:.index|address:user1", ""),
+        ("str:players|u64:1|str:.index|address:user2", ""),
+        ("str:gamesPerUser|address:user1|str:.len", ""),
+        ("str:gamesPerUser|address:user1|str:.item|u32:1", ""),
+        ("str:gamesPerUser|address:user2|str:.len", ""),
+        ("str:gamesPerUser|address:user2|str:.item|u32:1", ""),
+        ("str:gameEscrow|u64:1", ""),
+    ]);
+
+    // only the compact result is kept
+    let game_result = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_result(1u64)
+        .returns(ReturnsResult)
+        .run();
+
+    assert_eq!(
+        game_result.creator,
+        ManagedAddress::from(OWNER_ADDR.eval_to_array())
+    );
+    assert_eq!(
+        game_result.token_id,
+        EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID)
+    );
+    assert_eq!(game_result.wager, BigUint::from(100u64));
+    assert_eq!(game_result.player_count, 2u64);
+    assert_eq!(game_result.rake, BigUint::from(20u64));
+    assert_eq!(game_result.rewards.len(), 1);
+    assert_eq!(
+        game_result.rewards.get(0).winner,
+        ManagedAddress::from(USER1_ADDR.eval_to_array())
+    );
+    assert_eq!(game_result.rewards.get(0).amount, BigUint::from(180u64));
+    assert_eq!(game_result.settled_at, 1u64);
+
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game already settled")),
+    );
+
+    // the info of an archived game is read from its result
+    let game_info = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_game_info(1u64)
+        .returns(ReturnsResult)
+        .run();
+
+    assert_eq!(game_info.game_id, 1u64);
+    assert_eq!(
+        game_info.game_settings.creator,
+        ManagedAddress::from(OWNER_ADDR.eval_to_array())
+    );
+    assert_eq!(game_info.game_settings.status, game_proxy::Status::Settled);
+    assert_eq!(game_info.player_count, 2u64);
+    assert!(game_info.players.is_empty());
+    assert_eq!(game_info.pool, BigUint::zero());
+    assert_eq!(game_info.time_remaining, 0u64);
+    assert_eq!(game_info.status, game_proxy::Status::Settled);
+
+    // an archived game doesn't fail the batch
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(100u64),
+        OWNER_ADDR,
+        2,
+    );
+
+    let mut game_ids = MultiValueEncoded::<StaticApi, u64>::new();
+    game_ids.push(1u64);
+    game_ids.push(2u64);
+    let games_info = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .get_games_info(game_ids)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    assert_eq!(games_info.len(), 2);
+    assert_eq!(games_info[0].game_id, 1u64);
+    assert_eq!(games_info[0].status, game_proxy::Status::Settled);
+    assert_eq!(games_info[1].game_id, 2u64);
+    assert_eq!(games_info[1].status, game_proxy::Status::Open);
+
+    // game 3 => cancelled, game 2 => refunded, both are archived too
+    state.join_game(
+        2u64,
+        USER3_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(100u64),
+        USER5_ADDR,
+        3,
+    );
+    state.join_game(
+        3u64,
+        USER4_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.cancel_game(3u64, USER5_ADDR, OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+
+    state.check_sc_storage(&[
+        ("str:gameSettings|u64:2", ""),
+        ("str:gameSettings|u64:3", ""),
+        ("str:players|u64:2|str:.len", ""),
+        ("str:players|u64:3|str:.len", ""),
+        ("str:gameEscrow|u64:2", ""),
+        ("str:gameEscrow|u64:3", ""),
+    ]);
+    for (game_id, status) in [
+        (2u64, game_proxy::Status::Refunded),
+        (3u64, game_proxy::Status::Cancelled),
+    ] {
+        let game_result = state
+            .world
+            .query()
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .game_result(game_id)
+            .returns(ReturnsResult)
+            .run();
+
+        assert_eq!(game_result.status, status);
+        assert_eq!(game_result.player_count, 1u64);
+        assert!(game_result.rewards.is_empty());
+        state.check_game_status(game_id, status);
+    }
+
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE));
+    state.send_reward(
+        2u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game already refunded")),
+    );
+    state.send_reward(
+        3u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game was cancelled")),
+    );
+    state.join_game(
+        3u64,
+        USER1_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::Some((4, "game is not open")),
+    );
+
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64]);
+}
+
+#[test]
+fn batched_settlement_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    // only the owner can set the batch size, which can't be 0
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_batch_size(2usize)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_batch_size(0usize)
+        .with_result(ExpectError(4, "batch size can't be 0"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_batch_size(2usize)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => 5 players
+    state.create_game(waiting_time, 2u64, 5u64, wager.clone(), OWNER_ADDR, 1);
+    for user in [USER1_ADDR, USER2_ADDR, USER3_ADDR, USER4_ADDR, USER5_ADDR] {
+        state.join_game(1u64, user, wager.clone(), OptionalValue::None);
+    }
+
+    // the first batch is processed by sendReward
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 6_000u64));
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 4_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settling);
+    state.check_settlement_progress(1u64, 2u64);
+
+    // the settlement can't be started again
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "settlement already started")),
+    );
+
+    // anyone can continue the settlement, with a batch size above 0
+    state.continue_settlement(
+        1u64,
+        0usize,
+        USER1_ADDR,
+        OptionalValue::Some((4, "batch size can't be 0")),
+    );
+
+    state.continue_settlement(1u64, 2usize, USER1_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settling);
+    state.check_settlement_progress(1u64, 4u64);
+
+    // the payouts of a settlement are stopped by the maintenance
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .run();
+    state.continue_settlement(
+        1u64,
+        10usize,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "maintenance")),
+    );
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .enable_sc()
+        .run();
+
+    // the last player closes the game
+    state.continue_settlement(1u64, 10usize, OWNER_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 200u64));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE + 100u64));
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.continue_settlement(
+        1u64,
+        2usize,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "no settlement in progress")),
+    );
+
+    // game 2 => not enough players, refunded in batches
+    state.create_game(waiting_time, 4u64, 5u64, wager.clone(), USER5_ADDR, 2);
+    for user in [USER1_ADDR, USER2_ADDR, USER3_ADDR] {
+        state.join_game(2u64, user, wager.clone(), OptionalValue::None);
+    }
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Settling);
+
+    // players can't claim back their wager while the refund is in progress
+    state.claim_back_wager(
+        2u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "can manually claim back wager only if the game is open")),
+    );
+
+    state.continue_settlement(2u64, 2usize, OWNER_ADDR, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Refunded);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 200u64));
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - 100u64));
+
+    state.check_escrow_invariant(&[1u64, 2u64]);
+}
+
+#[test]
+fn pull_payouts_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+    let game_token = EgldOrEsdtTokenIdentifier::<StaticApi>::esdt(TOKEN_GAME_ID);
+    let other_token = EgldOrEsdtTokenIdentifier::<StaticApi>::esdt(TOKEN_OTHER_ID);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .add_accepted_token(other_token.clone())
+        .run();
+
+    // only the owner can set the payout mode
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_pull_payouts(true)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 and 3 => main token, game 2 => other token, game 4 => not enough players
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game_with_payment(
+        waiting_time,
+        2u64,
+        2u64,
+        wager.clone(),
+        OWNER_ADDR,
+        &other_token,
+        STARTING_FEE,
+        OptionalValue::None,
+    );
+    state.join_game_with_token(
+        2u64,
+        USER1_ADDR,
+        &other_token,
+        wager.clone(),
+        OptionalValue::None,
+    );
+    state.join_game_with_token(
+        2u64,
+        USER3_ADDR,
+        &other_token,
+        wager.clone(),
+        OptionalValue::None,
+    );
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 3);
+    state.join_game(3u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(3u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game(waiting_time, 3u64, 3u64, wager.clone(), USER5_ADDR, 4);
+    state.join_game(4u64, USER4_ADDR, wager, OptionalValue::None);
+
+    // push => user1 is paid right away (200 won, 200 paid for games 1 and 3)
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    assert!(state.get_claimable(USER1_ADDR).is_empty());
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_pull_payouts(true)
+        .run();
+
+    // pull => rewards, returned wagers and the returned start fee are kept for each token
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(2u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.send_reward(3u64, OptionalValue::None, OptionalValue::None);
+
+    // set now = 102, past waiting time
+    state.world.current_block().block_timestamp(102u64);
+    state.send_reward(4u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(4u64, game_proxy::Status::Refunded);
+
+    state
+        .world
+        .check_account(USER1_ADDR)
+        .esdt_balance(TOKEN_OTHER, BALANCE - 100u64);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+
+    let user1_claimable = state.get_claimable(USER1_ADDR);
+    assert_eq!(user1_claimable.len(), 2);
+    assert!(user1_claimable.contains(&(game_token.clone(), RustBigUint::from(100u64))));
+    assert!(user1_claimable.contains(&(other_token.clone(), RustBigUint::from(200u64))));
+    assert_eq!(
+        state.get_claimable(USER2_ADDR),
+        vec![(game_token.clone(), RustBigUint::from(100u64))]
+    );
+    assert_eq!(
+        state.get_claimable(USER4_ADDR),
+        vec![(game_token.clone(), RustBigUint::from(100u64))]
+    );
+    assert_eq!(
+        state.get_claimable(USER5_ADDR),
+        vec![(game_token, RustBigUint::from(STARTING_FEE))]
+    );
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64, 4u64]);
+
+    // user3 lost => nothing to claim
+    state
+        .world
+        .tx()
+        .from(USER3_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .claim_rewards()
+        .with_result(ExpectError(4, "nothing to claim"))
+        .run();
+
+    // user1 claims in both tokens at once
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .claim_rewards()
+        .run();
+    state
+        .world
+        .check_account(USER1_ADDR)
+        .esdt_balance(TOKEN_OTHER, BALANCE + 100u64);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 100u64));
+    assert!(state.get_claimable(USER1_ADDR).is_empty());
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .claim_rewards()
+        .with_result(ExpectError(4, "nothing to claim"))
+        .run();
+
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64, 4u64]);
+}
+
+#[test]
+fn signed_result_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+    let signer_secret = [7u8; 32];
+    let other_secret = [9u8; 32];
+
+    // deploy
+    state.deploy();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // 2 full games, no admin set
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER4_ADDR, wager, OptionalValue::None);
+
+    let winners = [(USER1_ADDR, 10_000u64)];
+    state.submit_signed_result(
+        1u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 1u64, 0u64, &winners),
+        &winners,
+        OptionalValue::Some((4, "result signer not set")),
+    );
+
+    // only the owner can set the signer, with a valid ed25519 public key
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_result_signer(signer_public_key(signer_secret))
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_result_signer(vec![1u8; 31])
+        .with_result(ExpectError(4, "invalid result signer public key"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_result_signer(signer_public_key(signer_secret))
+        .run();
+
+    // signed by another key => fails
+    state.submit_signed_result(
+        1u64,
+        USER5_ADDR,
+        sign_result(other_secret, 1u64, 0u64, &winners),
+        &winners,
+        OptionalValue::Some((10, "invalid signature")),
+    );
+
+    // winners changed after signing => fails
+    state.submit_signed_result(
+        1u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 1u64, 0u64, &winners),
+        &[(USER2_ADDR, 10_000u64)],
+        OptionalValue::Some((10, "invalid signature")),
+    );
+
+    // signed for another game => fails
+    state.submit_signed_result(
+        2u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 1u64, 0u64, &[]),
+        &[],
+        OptionalValue::Some((10, "invalid signature")),
+    );
+
+    // anyone can submit a valid result
+    state.submit_signed_result(
+        1u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 1u64, 0u64, &winners),
+        &winners,
+        OptionalValue::None,
+    );
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 100u64));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE - 100u64));
+
+    // replay => fails
+    state.submit_signed_result(
+        1u64,
+        USER2_ADDR,
+        sign_result(signer_secret, 1u64, 0u64, &winners),
+        &winners,
+        OptionalValue::Some((10, "invalid signature")),
+    );
+
+    // signed tie/draw => the wagers are returned
+    state.submit_signed_result(
+        2u64,
+        USER3_ADDR,
+        sign_result(signer_secret, 2u64, 0u64, &[]),
+        &[],
+        OptionalValue::None,
+    );
+    state.check_game_status(2u64, game_proxy::Status::Settled);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .signed_result_nonce(2u64)
+        .returns(ExpectValue(1u64))
+        .run();
+
+    // game 3 => the signed result is rejected after a dispute, a corrected one can be submitted
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_challenge_period(50u64)
+        .run();
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(100u64),
+        OWNER_ADDR,
+        3,
+    );
+    state.join_game(
+        3u64,
+        USER1_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.join_game(
+        3u64,
+        USER2_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+
+    state.submit_signed_result(
+        3u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 3u64, 0u64, &winners),
+        &winners,
+        OptionalValue::None,
+    );
+    state.dispute_result(3u64, USER2_ADDR, OptionalValue::None);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .resolve_dispute(3u64, false)
+        .run();
+
+    // the rejected result can't be replayed
+    state.submit_signed_result(
+        3u64,
+        USER1_ADDR,
+        sign_result(signer_secret, 3u64, 0u64, &winners),
+        &winners,
+        OptionalValue::Some((10, "invalid signature")),
+    );
+
+    let corrected_winners = [(USER2_ADDR, 10_000u64)];
+    state.submit_signed_result(
+        3u64,
+        USER5_ADDR,
+        sign_result(signer_secret, 3u64, 1u64, &corrected_winners),
+        &corrected_winners,
+        OptionalValue::None,
+    );
+
+    // set now = 52, past the challenge period
+    state.world.current_block().block_timestamp(52u64);
+    state.finalize_result(3u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(3u64, game_proxy::Status::Settled);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE));
+}
+
+#[test]
+fn settlement_quorum_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let token_id = EgldOrEsdtTokenIdentifier::<StaticApi>::esdt(TOKEN_GAME_ID);
+
+    // deploy, 3 admins
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state.set_admin(USER4_ADDR);
+    state.set_admin(USER5_ADDR);
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_quorum_threshold(token_id.clone(), 150u64)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_required_approvals(0usize)
+        .with_result(ExpectError(4, "required approvals can't be 0"))
+        .run();
+
+    // total wager above 150 => 2 approvals needed, valid for 100 seconds
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_quorum_threshold(token_id, 150u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_required_approvals(2usize)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_proposal_duration(100u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => total wager 200, game 2 => total wager 100
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(100u64),
+        OWNER_ADDR,
+        1,
+    );
+    state.join_game(
+        1u64,
+        USER1_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.join_game(
+        1u64,
+        USER2_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::None,
+    );
+    state.create_game(
+        waiting_time,
+        2u64,
+        2u64,
+        RustBigUint::from(50u64),
+        OWNER_ADDR,
+        2,
+    );
+    state.join_game(
+        2u64,
+        USER2_ADDR,
+        RustBigUint::from(50u64),
+        OptionalValue::None,
+    );
+    state.join_game(
+        2u64,
+        USER3_ADDR,
+        RustBigUint::from(50u64),
+        OptionalValue::None,
+    );
+
+    // below the threshold => paid right away
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER3_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(2u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Settled);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE + 50u64));
+
+    // above the threshold => proposal, nothing is paid yet
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners.clone()),
+        OptionalValue::None,
+    );
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE - 100u64));
+    assert_eq!(state.get_pending_proposals(), vec![1u64]);
+    state.check_proposal_approvers(1u64, &[OWNER_ADDR]);
+
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners.clone()),
+        OptionalValue::Some((4, "settlement proposal already pending")),
+    );
+    state.execute_settlement(
+        1u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "not enough approvals")),
+    );
+    state.approve_settlement(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "Item not whitelisted")),
+    );
+    state.approve_settlement(
+        1u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "settlement already approved")),
+    );
+    state.expire_settlement(
+        1u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "settlement proposal not expired yet")),
+    );
+
+    // set now = 102, the proposal expired
+    state.world.current_block().block_timestamp(102u64);
+    state.approve_settlement(
+        1u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "settlement proposal expired")),
+    );
+    state.expire_settlement(1u64, USER4_ADDR, OptionalValue::None);
+    assert!(state.get_pending_proposals().is_empty());
+    state.approve_settlement(
+        1u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "no settlement proposal for game id")),
+    );
+
+    // the winners are validated when the proposal is made
+    let mut invalid_winners =
+        MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    invalid_winners.push((ManagedAddress::from(USER3_ADDR.eval_to_array()), 10_000u64));
+    state
+        .world
+        .tx()
+        .from(USER5_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .send_reward(1u64, OptionalValue::Some(invalid_winners))
+        .with_result(ExpectError(4, "winner has not joined the game"))
+        .run();
+
+    // new proposal, approved by user5 and the owner
+    state
+        .world
+        .tx()
+        .from(USER5_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .send_reward(1u64, OptionalValue::Some(winners))
+        .run();
+    state.approve_settlement(1u64, OWNER_ADDR, OptionalValue::None);
+    state.check_proposal_approvers(1u64, &[USER5_ADDR, OWNER_ADDR]);
+
+    // approvals of removed admins don't count
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .remove_admin(USER5_ADDR)
+        .run();
+    state.execute_settlement(
+        1u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "not enough approvals")),
+    );
+
+    state.approve_settlement(1u64, USER4_ADDR, OptionalValue::None);
+    state.execute_settlement(1u64, USER4_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 100u64));
+    assert!(state.get_pending_proposals().is_empty());
+
+    state.execute_settlement(
+        1u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "no settlement proposal for game id")),
+    );
+}
+
+#[test]
+fn dispute_window_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_challenge_period(50u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER4_ADDR, wager, OptionalValue::None);
+
+    // the result of game 1 is kept until now = 51
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners.clone()),
+        OptionalValue::None,
+    );
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.send_reward(
+        1u64,
+        OptionalValue::Some(winners),
+        OptionalValue::Some((4, "result already submitted")),
+    );
+    state.finalize_result(
+        1u64,
+        USER5_ADDR,
+        OptionalValue::Some((4, "challenge period is not over yet")),
+    );
+
+    // only the players of the game can dispute the result, once
+    state.dispute_result(
+        1u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "caller has not joined the game")),
+    );
+    state.dispute_result(1u64, USER2_ADDR, OptionalValue::None);
+    state.dispute_result(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "result already disputed")),
+    );
+
+    // tie/draw for game 2, the disputes can't be paused
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.set_operation_paused(game_proxy::Operation::Claims, true);
+    state.dispute_result(2u64, USER3_ADDR, OptionalValue::None);
+    state.set_operation_paused(game_proxy::Operation::Claims, false);
+
+    // set now = 52, the disputed results stay frozen
+    state.world.current_block().block_timestamp(52u64);
+    state.finalize_result(
+        1u64,
+        USER5_ADDR,
+        OptionalValue::Some((4, "result is disputed")),
+    );
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE - 100u64));
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .resolve_dispute(1u64, false)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    // the owner rejects the result of game 1 and upholds the one of game 2
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .resolve_dispute(1u64, false)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .resolve_dispute(2u64, true)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .resolve_dispute(2u64, true)
+        .with_result(ExpectError(4, "result is not disputed"))
+        .run();
+    state.dispute_result(
+        2u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "result already disputed")),
+    );
+
+    state.finalize_result(2u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Settled);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE));
+
+    // new result for game 1, kept until now = 102
+    state.finalize_result(
+        1u64,
+        USER5_ADDR,
+        OptionalValue::Some((4, "no pending result for game id")),
+    );
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 10_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+
+    // set now = 103, too late to dispute
+    state.world.current_block().block_timestamp(103u64);
+    state.dispute_result(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "challenge period is over")),
+    );
+
+    state.finalize_result(1u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE + 100u64));
+}
+
+#[test]
+fn roles_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+
+    // only the owner can grant roles
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .grant_role(USER1_ADDR, game_proxy::Role::ConfigManager)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    for (user, role) in [
+        (USER1_ADDR, game_proxy::Role::ConfigManager),
+        (USER2_ADDR, game_proxy::Role::Pauser),
+        (USER3_ADDR, game_proxy::Role::Treasurer),
+        (USER4_ADDR, game_proxy::Role::Settler),
+    ] {
+        state
+            .world
+            .tx()
+            .from(OWNER_ADDR)
+            .to(GAME_SC_ADDR)
+            .typed(game_proxy::MvxGameScProxy)
+            .grant_role(user, role)
+            .run();
+    }
+
+    state.check_roles(USER1_ADDR, vec![game_proxy::Role::ConfigManager]);
+    state.check_roles(USER4_ADDR, vec![game_proxy::Role::Settler]);
+    state.check_roles(USER5_ADDR, vec![]);
+
+    // the settler role is the admins whitelist
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .is_user_admin(USER4_ADDR)
+        .returns(ExpectValue(true))
+        .run();
+
+    // config manager
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(1_000u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_challenge_period(10u64)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    // pauser
+    state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(0u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER2_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .enable_sc()
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // settler => 10% rake of the 200 total wager
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 10_000u64));
+    state
+        .world
+        .tx()
+        .from(USER4_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .send_reward(1u64, OptionalValue::Some(winners))
+        .run();
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+
+    // treasurer
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(TOKEN_GAME_ID, OptionalValue::<BigUint<StaticApi>>::None)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER3_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .withdraw_rake(TOKEN_GAME_ID, OptionalValue::<BigUint<StaticApi>>::None)
+        .run();
+
+    // the rake goes to the owner, not to the treasurer
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(
+        OWNER_ADDR,
+        RustBigUint::from(BALANCE - STARTING_FEE + 20u64),
+    );
+
+    // revoked roles
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .revoke_role(USER1_ADDR, game_proxy::Role::ConfigManager)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .revoke_role(USER4_ADDR, game_proxy::Role::Settler)
+        .run();
+    state.check_roles(USER1_ADDR, vec![]);
+    state.check_roles(USER4_ADDR, vec![]);
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_rake(0u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(USER4_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .approve_settlement(1u64)
+        .with_result(ExpectError(4, "Item not whitelisted"))
+        .run();
+}
+
+#[test]
+fn pause_controls_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state.check_pause_matrix([false, false, false, false]);
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .pause_operation(game_proxy::Operation::Creation)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // creation paused
+    state.set_operation_paused(game_proxy::Operation::Creation, true);
+    state.check_pause_matrix([true, false, false, false]);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .create_game(
+            waiting_time,
+            2u64,
+            3u64,
+            100u64,
+            OptionalValue::<u64>::None,
+            OptionalValue::<u64>::None,
+        )
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            0u64,
+            &BigUint::from(STARTING_FEE),
+        )
+        .with_result(ExpectError(4, "operation paused"))
+        .run();
+    state.set_operation_paused(game_proxy::Operation::Creation, false);
+
+    // game 1 => 2 players out of 3, game 2 => full
+    state.create_game(waiting_time, 2u64, 3u64, wager.clone(), OWNER_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER4_ADDR, wager.clone(), OptionalValue::None);
+
+    // joining paused
+    state.set_operation_paused(game_proxy::Operation::Joining, true);
+    state.join_game(
+        1u64,
+        USER5_ADDR,
         wager.clone(),
-        OWNER_ADDR,
+        OptionalValue::Some((4, "operation paused")),
+    );
+
+    // maintenance => everything but the claims is paused
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .disable_sc()
+        .run();
+    state.check_pause_matrix([true, true, true, false]);
+    state.send_reward(
+        2u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "maintenance")),
+    );
+
+    // players can still leave during maintenance
+    state.leave_game(1u64, USER2_ADDR, OptionalValue::None);
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE));
+
+    // set now = 102, game 1 did not reach the min number of players
+    state.world.current_block().block_timestamp(102u64);
+
+    // claims paused explicitly
+    state.set_operation_paused(game_proxy::Operation::Claims, true);
+    state.check_pause_matrix([true, true, true, true]);
+    state.claim_back_wager(
         1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "operation paused")),
     );
 
-    // check last game id, needs to be 1
+    state.set_operation_paused(game_proxy::Operation::Claims, false);
+    state.claim_back_wager(1u64, USER1_ADDR, OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+
+    // back from maintenance, joining still paused
     state
         .world
-        .query()
+        .tx()
+        .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .last_game_id()
-        .with_result(ExpectValue(1u64))
+        .enable_sc()
         .run();
+    state.check_pause_matrix([false, true, false, false]);
 
-    // user1 tries to join the game, timestamp is ok, max players not reached, should work
-    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    // settlement paused
+    state.set_operation_paused(game_proxy::Operation::Settlement, true);
+    state.send_reward(
+        2u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "operation paused")),
+    );
 
-    // min number of players reached, game should be valid
-    let game_setting = state
+    state.set_operation_paused(game_proxy::Operation::Settlement, false);
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Settled);
+}
+
+#[test]
+fn emergency_mode_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    state
         .world
         .tx()
         .from(USER1_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .game_settings(1u64)
-        .returns(ReturnsResultUnmanaged)
+        .set_emergency_timeout(1_000u64)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_emergency_timeout(1_000u64)
         .run();
-    let game_settings = SingleValue::from(game_setting);
-    assert_eq!(game_settings.into().status, game_proxy::Status::Valid);
 
-    // user2 tries to join the game, shuld work
-    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
 
-    // both users should be in players vec
-    let players = state
+    // game 1 => valid, created by user5, game 2 => not enough players
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+
+    // set now = 1101, the timeout is not over yet
+    state.world.current_block().block_timestamp(1_101u64);
+    state.emergency_withdraw(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "emergency mode is not active")),
+    );
+    state.emergency_withdraw(
+        2u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "emergency mode is not active")),
+    );
+
+    // set now = 1102, game 1 unsettled for too long => emergency mode
+    state.world.current_block().block_timestamp(1_102u64);
+    state.emergency_withdraw(1u64, USER1_ADDR, OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .emergency_mode()
+        .returns(ExpectValue(true))
+        .run();
+
+    // no new games, players or results
+    state
         .world
         .tx()
-        .from(USER2_ADDR)
+        .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .players(1u64)
-        .returns(ReturnsResult)
+        .create_game(
+            waiting_time,
+            2u64,
+            2u64,
+            100u64,
+            OptionalValue::<u64>::None,
+            OptionalValue::<u64>::None,
+        )
+        .egld_or_single_esdt(
+            &EgldOrEsdtTokenIdentifier::esdt(TOKEN_GAME_ID),
+            0u64,
+            &BigUint::from(STARTING_FEE),
+        )
+        .with_result(ExpectError(4, "emergency mode"))
         .run();
-    let players_vec = players.to_vec();
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "emergency mode")),
+    );
 
-    assert!(players_vec.contains(&ManagedAddress::from(USER1_ADDR.eval_to_array())));
-    assert!(players_vec.contains(&ManagedAddress::from(USER2_ADDR.eval_to_array())));
+    // every game not settled yet can be refunded
+    state.emergency_withdraw(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "nothing to withdraw")),
+    );
+    state.emergency_withdraw(
+        1u64,
+        USER4_ADDR,
+        OptionalValue::Some((4, "nothing to withdraw")),
+    );
+    state.emergency_withdraw(2u64, USER3_ADDR, OptionalValue::None);
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE));
 
-    // game should be in users'storage
-    let games_per_user1: MultiValueEncoded<StaticApi, u64> = state
+    // the creator only gets back the start fee
+    state.emergency_withdraw(1u64, USER5_ADDR, OptionalValue::None);
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE));
+    state.check_game_escrow(1u64, 100u64);
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+
+    state.emergency_withdraw(1u64, USER2_ADDR, OptionalValue::None);
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE));
+    state.check_game_status(1u64, game_proxy::Status::Refunded);
+    state.emergency_withdraw(
+        1u64,
+        USER2_ADDR,
+        OptionalValue::Some((4, "game is already closed")),
+    );
+
+    state.emergency_withdraw(2u64, OWNER_ADDR, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Refunded);
+    state.check_escrow_invariant(&[1u64, 2u64]);
+
+    // back to normal
+    state
         .world
         .tx()
-        .from(USER2_ADDR)
+        .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .games_per_user(USER1_ADDR.eval_to_array())
-        .returns(ReturnsResult)
+        .set_emergency_mode(false)
         .run();
-    let games_per_user1_vec = games_per_user1.to_vec();
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 3);
+    state.join_game(3u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(3u64, USER2_ADDR, wager, OptionalValue::None);
 
-    let games_per_user2: MultiValueEncoded<StaticApi, u64> = state
+    // emergency mode turned on by the owner, the claims being paused doesn't matter
+    state
         .world
         .tx()
-        .from(USER2_ADDR)
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_emergency_mode(true)
+        .run();
+    state.set_operation_paused(game_proxy::Operation::Claims, true);
+    state.emergency_withdraw(3u64, USER1_ADDR, OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    state.check_escrow_invariant(&[1u64, 2u64, 3u64]);
+}
+
+#[test]
+fn emergency_settling_game_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy, batches of 2 players
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_emergency_timeout(1_000u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_batch_size(2usize)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 => settled in batches, game 2 => cancelled in batches
+    state.create_game(waiting_time, 2u64, 5u64, wager.clone(), OWNER_ADDR, 1);
+    for user in [USER1_ADDR, USER2_ADDR, USER3_ADDR, USER4_ADDR, USER5_ADDR] {
+        state.join_game(1u64, user, wager.clone(), OptionalValue::None);
+    }
+    state.create_game(waiting_time, 2u64, 5u64, wager.clone(), USER5_ADDR, 2);
+    for user in [USER1_ADDR, USER2_ADDR, USER3_ADDR, USER4_ADDR] {
+        state.join_game(2u64, user, wager.clone(), OptionalValue::None);
+    }
+
+    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
+    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 6_000u64));
+    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 4_000u64));
+    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
+    state.cancel_game(2u64, USER5_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settling);
+    state.check_game_status(2u64, game_proxy::Status::Settling);
+
+    // the operator pauses everything and disappears
+    state.set_operation_paused(game_proxy::Operation::Settlement, true);
+    state.set_operation_paused(game_proxy::Operation::Claims, true);
+
+    // set now = 1101, the timeout is not over yet
+    state.world.current_block().block_timestamp(1_101u64);
+    state.emergency_withdraw(
+        1u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "emergency mode is not active")),
+    );
+
+    // set now = 1102, the remaining players are paid what the settlements fixed
+    state.world.current_block().block_timestamp(1_102u64);
+    state.emergency_withdraw(1u64, USER3_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settling);
+    state.check_settlement_progress(1u64, 4u64);
+    state.emergency_withdraw(1u64, USER3_ADDR, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
+    state.emergency_withdraw(2u64, USER1_ADDR, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Cancelled);
+
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 200u64));
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE + 100u64));
+    state.check_balance(USER3_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER4_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE - 100u64));
+    state.check_sc_storage(&[
+        ("str:players|u64:1|str:.len", ""),
+        ("str:players|u64:2|str:.len", ""),
+    ]);
+
+    state.emergency_withdraw(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "game is already closed")),
+    );
+    state.check_escrow_invariant(&[1u64, 2u64]);
+}
+
+#[test]
+fn settlement_deadline_test() {
+    let mut state = GameContractState::new();
+
+    let waiting_time = 100u64;
+    let wager = RustBigUint::from(100u64);
+
+    // deploy
+    state.deploy();
+    state.set_admin(OWNER_ADDR);
+
+    state
+        .world
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_timeout(500u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_settlement_timeout(500u64)
+        .run();
+
+    // set now = 1
+    state.world.current_block().block_timestamp(1u64);
+
+    // game 1 created by user5, deadline = 101 + 500
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), USER5_ADDR, 1);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
+
+    // set now = 601, the game can still be settled
+    state.world.current_block().block_timestamp(601u64);
+    state.claim_back_wager(
+        1u64,
+        USER1_ADDR,
+        OptionalValue::Some((4, "settlement deadline is not over yet")),
+    );
+
+    // set now = 602, no result anymore, the players get back their wager
+    state.world.current_block().block_timestamp(602u64);
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "settlement deadline has passed")),
+    );
+    state.claim_back_wager(1u64, USER1_ADDR, OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE));
+    state.check_game_status(1u64, game_proxy::Status::InProgress);
+
+    // the last player closes the game, the creator gets back the start fee
+    state.claim_back_wager(1u64, USER2_ADDR, OptionalValue::None);
+    state.check_balance(USER2_ADDR, RustBigUint::from(BALANCE));
+    state.check_balance(USER5_ADDR, RustBigUint::from(BALANCE));
+    state.check_game_status(1u64, game_proxy::Status::Refunded);
+    state.check_game_escrow(1u64, 0u64);
+
+    // games created without a timeout keep waiting for the result
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .games_per_user(USER2_ADDR.eval_to_array())
-        .returns(ReturnsResult)
+        .set_settlement_timeout(0u64)
         .run();
-    let games_per_user2_vec = games_per_user2.to_vec();
+    state.create_game(waiting_time, 2u64, 2u64, wager.clone(), OWNER_ADDR, 2);
+    state.join_game(2u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(2u64, USER4_ADDR, wager, OptionalValue::None);
 
-    assert!((games_per_user1_vec.contains(&1u64) && games_per_user2_vec.contains(&1u64)));
+    state.world.current_block().block_timestamp(10_000u64);
+    state.claim_back_wager(
+        2u64,
+        USER3_ADDR,
+        OptionalValue::Some((4, "can manually claim back wager only if the game is open")),
+    );
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_escrow_invariant(&[1u64, 2u64]);
 }
 
 #[test]
-fn game_sc_complex_flow() {
+fn scheduled_game_test() {
     let mut state = GameContractState::new();
 
-    // game settings
-    let waiting_time = 100u64; // => timestamp 102 should be out of waiting time
-    let number_of_players_min = 2u64;
-    let number_of_players_max = 4u64;
     let wager = RustBigUint::from(100u64);
-    let diff_amount = RustBigUint::from(5u64);
 
     // deploy
     state.deploy();
+    state.set_admin(OWNER_ADDR);
 
-    // set now = 1
-    state.world.current_block().block_timestamp(1u64);
-
-    // create first game
-    state.create_game(
-        waiting_time,
-        number_of_players_min,
-        number_of_players_max,
-        wager.clone(),
-        OWNER_ADDR,
-        1,
-    );
-
-    // user1 joins
-    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
-
-    let game_setting = state
+    state
         .world
         .tx()
         .from(USER1_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .game_settings(1u64)
-        .returns(ReturnsResultUnmanaged)
+        .set_schedule_bounds(60u64, 1_000u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_schedule_bounds(1_000u64, 60u64)
+        .with_result(ExpectError(4, "invalid schedule bounds"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_schedule_bounds(60u64, 1_000u64)
         .run();
-    let game_settings = SingleValue::from(game_setting);
 
-    assert_eq!(game_settings.into().status, game_proxy::Status::Invalid);
+    // set now = 100
+    state.world.current_block().block_timestamp(100u64);
 
-    // user1 tries to claim back wager, should fail (waiting time not passed)
-    state.claim_back_wager(
-        1u64,
-        USER1_ADDR,
-        OptionalValue::Some((4, "waiting time is not over yet")),
+    state.create_scheduled_game(
+        50u64,
+        200u64,
+        0u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "waiting time and join deadline can't both be set")),
+    );
+    state.create_scheduled_game(
+        0u64,
+        100u64,
+        0u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "timestamp is in the past")),
+    );
+    state.create_scheduled_game(
+        0u64,
+        159u64,
+        0u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "timestamp is too soon")),
+    );
+    state.create_scheduled_game(
+        0u64,
+        1_101u64,
+        0u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "timestamp is too far")),
+    );
+    state.create_scheduled_game(
+        0u64,
+        500u64,
+        400u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "start time can't be before the join deadline")),
+    );
+    state.create_scheduled_game(
+        0u64,
+        500u64,
+        1_200u64,
+        OWNER_ADDR,
+        OptionalValue::Some((4, "timestamp is too far")),
     );
 
-    // user2 joins
-    state.join_game(
-        1u64,
-        USER2_ADDR,
-        diff_amount,
-        OptionalValue::Some((4, "wrong amount paid")),
-    ); // wrong amount paid
-
-    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
-    state.join_game(
-        1u64,
-        USER2_ADDR,
-        wager.clone(),
-        OptionalValue::Some((4, "user already joined this game")),
-    ); // user already joined
-
-    let game_setting = state
+    // game 1 => players can join until 500, the game starts at 800
+    state.create_scheduled_game(0u64, 500u64, 800u64, OWNER_ADDR, OptionalValue::None);
+    let settings = state
         .world
-        .tx()
-        .from(USER1_ADDR)
+        .query()
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
         .game_settings(1u64)
         .returns(ReturnsResultUnmanaged)
         .run();
-    let game_settings = SingleValue::from(game_setting);
+    assert_eq!(settings.time_limit, 500u64);
+    assert_eq!(settings.join_deadline, 500u64);
+    assert_eq!(settings.start_time, 800u64);
 
-    assert_eq!(game_settings.into().status, game_proxy::Status::Valid);
+    state.world.current_block().block_timestamp(500u64);
+    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
+    state.join_game(1u64, USER2_ADDR, wager.clone(), OptionalValue::None);
 
-    // user3 joins
-    state.join_game(1u64, USER3_ADDR, wager.clone(), OptionalValue::None);
+    // no result before the scheduled start
+    state.world.current_block().block_timestamp(799u64);
+    state.send_reward(
+        1u64,
+        OptionalValue::None,
+        OptionalValue::Some((4, "game has not started yet")),
+    );
 
-    // set timestamp after time limit
-    state.world.current_block().block_timestamp(102u64);
+    state.world.current_block().block_timestamp(800u64);
+    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(1u64, game_proxy::Status::Settled);
 
-    // user4 joins, time has passed, fails
+    // game 2 => only a scheduled start, the waiting time is still used
+    state.create_scheduled_game(100u64, 0u64, 1_500u64, OWNER_ADDR, OptionalValue::None);
+    state.world.current_block().block_timestamp(901u64);
     state.join_game(
-        1u64,
-        USER4_ADDR,
+        2u64,
+        USER1_ADDR,
         wager,
         OptionalValue::Some((4, "waiting time has passed")),
     );
+}
 
-    // user4 tries to claim back wager, fails
-    state.claim_back_wager(
-        1u64,
-        USER4_ADDR,
-        OptionalValue::Some((4, "caller has not joined the game")),
-    );
+#[test]
+fn game_limits_test() {
+    let mut state = GameContractState::new();
 
-    // user1 tries to claim back wager, fails
-    state.claim_back_wager(
-        1u64,
-        USER1_ADDR,
-        OptionalValue::Some((
-            4,
-            "can manually claim back wager only if the game is invalid",
-        )),
-    );
+    let token_id = state.token_id.clone();
+    let wager = RustBigUint::from(100u64);
 
-    // send tokens to sc
-    state.world.transfer_step(
-        TransferStep::new()
-            .from(OWNER_ADDR.eval_to_expr().as_str())
-            .to(GAME_SC_ADDR.eval_to_expr().as_str())
-            .esdt_transfer(TOKEN_GAME_ID, 0, "10_000"),
-    );
+    // deploy
+    state.deploy();
 
     state
         .world
-        .check_account(GAME_SC_ADDR)
-        .esdt_balance(TOKEN_GAME, 10_320u64);
-
-    // owner sends rewards
-    let mut winners = MultiValueEncoded::<StaticApi, (ManagedAddress<StaticApi>, u64)>::new();
-    winners.push((ManagedAddress::from(USER1_ADDR.eval_to_array()), 8000u64)); // 80%
-    winners.push((ManagedAddress::from(USER2_ADDR.eval_to_array()), 2000u64)); // 20%
-
-    // make owner admin first
+        .tx()
+        .from(USER1_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_player_limits(2u64, 4u64)
+        .with_result(ExpectError(4, "caller does not have the required role"))
+        .run();
     state
         .world
         .tx()
         .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .set_admin(OWNER_ADDR)
+        .set_waiting_time_limits(3_600u64, 60u64)
+        .with_result(ExpectError(4, "invalid limits"))
         .run();
 
-    // send reward
-    state.send_reward(1u64, OptionalValue::Some(winners), OptionalValue::None);
-
-    // user1 should receive 80% of the reward
-    // reward = 3 * wager = 300 => user1's reward = 240
     state
         .world
-        .check_account(USER1_ADDR)
-        .esdt_balance(TOKEN_GAME, 100000140);
-
-    // user2's reward = 60
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_waiting_time_limits(60u64, 3_600u64)
+        .run();
     state
         .world
-        .check_account(USER2_ADDR)
-        .esdt_balance(TOKEN_GAME, 99999960); // balance - wager + 60
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_player_limits(2u64, 4u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_min_wager(token_id.clone(), 10u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .set_token_max_wager(token_id.clone(), 1_000u64)
+        .run();
+
+    let limits = state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .game_limits(token_id.clone())
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(limits.waiting_time_min, 60u64);
+    assert_eq!(limits.waiting_time_max, 3_600u64);
+    assert_eq!(limits.number_of_players_min, 2u64);
+    assert_eq!(limits.number_of_players_max, 4u64);
+    assert_eq!(limits.wager_min, 10u64);
+    assert_eq!(limits.wager_max, 1_000u64);
+
+    let failures = [
+        (59u64, 2u64, 4u64, 100u64, "waiting time too short"),
+        (3_601u64, 2u64, 4u64, 100u64, "waiting time too long"),
+        (100u64, 1u64, 4u64, 100u64, "min number of players too low"),
+        (100u64, 2u64, 5u64, 100u64, "max number of players too high"),
+        (100u64, 2u64, 4u64, 9u64, "wager too low"),
+        (100u64, 2u64, 4u64, 1_001u64, "wager too high"),
+    ];
+    for (waiting_time, min, max, wager, error) in failures {
+        state.create_game_with_payment(
+            waiting_time,
+            min,
+            max,
+            RustBigUint::from(wager),
+            OWNER_ADDR,
+            &token_id,
+            STARTING_FEE,
+            OptionalValue::Some((4, error)),
+        );
+    }
+
+    // the bounds are inclusive
+    state.create_game(60u64, 4u64, 2u64, wager, OWNER_ADDR, 1);
+    state.create_game(
+        3_600u64,
+        2u64,
+        4u64,
+        RustBigUint::from(1_000u64),
+        OWNER_ADDR,
+        2,
+    );
+}
+
+// storage entries of an UnorderedSetMapper holding the given items
+fn set_mapper_storage(key: &str, items: &[&str]) -> Vec<(String, String)> {
+    let mut storage = vec![(format!("{key}|str:.len"), items.len().to_string())];
+    for (index, item) in items.iter().enumerate() {
+        storage.push((
+            format!("{key}|str:.item|u32:{}", index + 1),
+            item.to_string(),
+        ));
+        storage.push((format!("{key}|str:.index|{item}"), (index + 1).to_string()));
+    }
+
+    storage
 }
 
 #[test]
-fn invalid_game_test() {
+fn legacy_migration_test() {
     let mut state = GameContractState::new();
 
-    // game settings
-    let waiting_time = 100u64; // => timestamp 102 should be out of waiting time
-    let number_of_players_min = 3u64;
-    let number_of_players_max = 5u64;
-    let wager = RustBigUint::from(100u64);
+    let legacy_game_2 = "u64:101|u64:2|u64:3|biguint:100|address:user5";
+    let legacy_game_3 = "u64:101|u64:2|u64:2|biguint:100|address:user5";
+    let legacy_game_4 = "u64:101|u64:2|u64:3|biguint:100|address:owner";
 
-    // deploy
-    state.deploy();
+    // game 1 => refunded by the legacy contract, game 2 => valid, game 3 => valid and paid,
+    // game 4 => not enough players yet, the legacy index was keyed by the settings at creation
+    let mut storage = vec![
+        ("str:tokenId".to_string(), "str:GAME-123456".to_string()),
+        ("str:gameStartFee".to_string(), "20".to_string()),
+        ("str:enabled".to_string(), "true".to_string()),
+        ("str:lastGameId".to_string(), "4".to_string()),
+        (
+            "str:gameSettings|u64:2".to_string(),
+            format!("{legacy_game_2}|u8:0"),
+        ),
+        (
+            "str:gameSettings|u64:3".to_string(),
+            format!("{legacy_game_3}|u8:0"),
+        ),
+        (
+            "str:gameSettings|u64:4".to_string(),
+            format!("{legacy_game_4}|u8:1"),
+        ),
+        (
+            format!("str:gameIdBySettings|{legacy_game_2}|u8:1"),
+            "2".to_string(),
+        ),
+        (
+            format!("str:gameIdBySettings|{legacy_game_3}|u8:1"),
+            "3".to_string(),
+        ),
+        (
+            format!("str:gameIdBySettings|{legacy_game_4}|u8:1"),
+            "4".to_string(),
+        ),
+    ];
+    storage.extend(set_mapper_storage(
+        "str:players|u64:2",
+        &["address:user1", "address:user2"],
+    ));
+    storage.extend(set_mapper_storage(
+        "str:players|u64:3",
+        &["address:user3", "address:user4"],
+    ));
+    storage.extend(set_mapper_storage("str:players|u64:4", &["address:user1"]));
+    storage.extend(set_mapper_storage(
+        "str:gamesPerUser|address:user1",
+        &["u64:2", "u64:4"],
+    ));
+    storage.extend(set_mapper_storage(
+        "str:gamesPerUser|address:user2",
+        &["u64:2"],
+    ));
+    storage.extend(set_mapper_storage(
+        "str:gamesPerUser|address:user3",
+        &["u64:3"],
+    ));
+    storage.extend(set_mapper_storage(
+        "str:gamesPerUser|address:user4",
+        &["u64:3"],
+    ));
+
+    // the wagers and start fees of games 2 and 4
+    state.deploy_legacy(&storage, 340u64);
 
     // set now = 1
-    state.world.current_block().block_timestamp(1);
+    state.world.current_block().block_timestamp(1u64);
 
-    // create game
-    state.create_game(
-        waiting_time,
-        number_of_players_min,
-        number_of_players_max,
-        wager.clone(),
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .upgrade()
+        .code(GAME_SC_PATH)
+        .run();
+    state
+        .world
+        .query()
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .legacy_last_game_id()
+        .returns(ExpectValue(4u64))
+        .run();
+
+    // nothing can be played until every legacy game is migrated
+    let token_id = state.token_id.clone();
+    state.create_game_with_payment(
+        100u64,
+        2u64,
+        3u64,
+        RustBigUint::from(100u64),
         OWNER_ADDR,
-        1,
+        &token_id,
+        STARTING_FEE,
+        OptionalValue::Some((4, "legacy games not migrated yet")),
     );
 
-    // user1 joins
-    state.join_game(1u64, USER1_ADDR, wager.clone(), OptionalValue::None);
-
-    // user2 joins
-    state.join_game(1u64, USER2_ADDR, wager, OptionalValue::None);
+    let mut paid_game_ids = MultiValueEncoded::<StaticApi, u64>::new();
+    paid_game_ids.push(3u64);
 
-    // game is still invalid, min number of players not reached
-    let game_setting = state
+    state
         .world
         .tx()
         .from(USER1_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .game_settings(1u64)
-        .returns(ReturnsResultUnmanaged)
+        .migrate_legacy_games(2usize, MultiValueEncoded::new())
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
         .run();
-    let game_settings = SingleValue::from(game_setting);
-
-    assert_eq!(game_settings.into().status, game_proxy::Status::Invalid);
-
-    // set now = 102, past waiting time
-    state.world.current_block().block_timestamp(102);
-
-    // make owner admin first
     state
         .world
         .tx()
         .from(OWNER_ADDR)
         .to(GAME_SC_ADDR)
         .typed(game_proxy::MvxGameScProxy)
-        .set_admin(OWNER_ADDR)
+        .migrate_legacy_games(2usize, paid_game_ids.clone())
+        .with_result(ExpectError(4, "paid game not in batch"))
         .run();
 
-    // send reward, invalid game => players should receive back wager, creator should receive the creation fee back
-    state.send_reward(1u64, OptionalValue::None, OptionalValue::None);
-
+    // first batch => games 1 and 2
     state
         .world
-        .check_account(USER1_ADDR)
-        .esdt_balance(TOKEN_GAME, 100000000);
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .migrate_legacy_games(2usize, MultiValueEncoded::new())
+        .run();
+    state.check_sc_storage(&[
+        (
+            format!("str:gameIdBySettings|{legacy_game_2}|u8:1").as_str(),
+            "",
+        ),
+        (
+            format!("str:gameIdBySettings|{legacy_game_3}|u8:1").as_str(),
+            "3",
+        ),
+        ("str:gameEscrow|u64:2", "220"),
+    ]);
+    state.check_game_status(2u64, game_proxy::Status::Ready);
+    state.check_game_id_by_creator(USER5_ADDR, 1, 2u64);
+    state.join_game(
+        4u64,
+        USER2_ADDR,
+        RustBigUint::from(100u64),
+        OptionalValue::Some((4, "legacy games not migrated yet")),
+    );
 
+    // second batch => game 3 was paid by the legacy contract and is archived
     state
         .world
-        .check_account(USER2_ADDR)
-        .esdt_balance(TOKEN_GAME, 100000000);
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .migrate_legacy_games(10usize, paid_game_ids.clone())
+        .run();
+    state.check_sc_storage(&[
+        (
+            format!("str:gameIdBySettings|{legacy_game_3}|u8:1").as_str(),
+            "",
+        ),
+        (
+            format!("str:gameIdBySettings|{legacy_game_4}|u8:1").as_str(),
+            "",
+        ),
+        ("str:gameSettings|u64:3", ""),
+        ("str:players|u64:3|str:.len", ""),
+        ("str:gamesPerUser|address:user3|str:.len", ""),
+        ("str:gameEscrow|u64:4", "120"),
+    ]);
+    state.check_game_status(3u64, game_proxy::Status::Settled);
+    state.check_game_status(4u64, game_proxy::Status::Open);
+    state.check_game_id_by_creator(USER5_ADDR, 2, 3u64);
+    state.check_game_id_by_creator(OWNER_ADDR, 1, 4u64);
+    state.check_escrow_invariant(&[2u64, 4u64]);
 
     state
         .world
-        .check_account(OWNER_ADDR)
-        .esdt_balance(TOKEN_GAME, 100000000);
+        .tx()
+        .from(OWNER_ADDR)
+        .to(GAME_SC_ADDR)
+        .typed(game_proxy::MvxGameScProxy)
+        .migrate_legacy_games(10usize, MultiValueEncoded::new())
+        .with_result(ExpectError(4, "no legacy games to migrate"))
+        .run();
+
+    // the migrated games are played as usual
+    state.set_admin(OWNER_ADDR);
+    state.world.current_block().block_timestamp(102u64);
+    state.send_reward(2u64, OptionalValue::None, OptionalValue::None);
+    state.check_game_status(2u64, game_proxy::Status::Settled);
+    state.claim_back_wager(4u64, USER1_ADDR, OptionalValue::None);
+    state.check_balance(USER1_ADDR, RustBigUint::from(BALANCE + 200));
+    state.create_game(100u64, 2u64, 3u64, RustBigUint::from(100u64), OWNER_ADDR, 5);
+    state.check_escrow_invariant(&[2u64, 4u64, 5u64]);
 }
@@ -5,9 +5,10 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                           19
+// Upgrade:                              1
+// Endpoints:                          107
 // Async Callback (empty):               1
-// Total number of exported functions:  21
+// Total number of exported functions: 110
 
 #![no_std]
 
@@ -18,25 +19,114 @@ multiversx_sc_wasm_adapter::endpoints! {
     mvx_game_sc
     (
         init => init
+        upgrade => upgrade
         createGame => create_game
         joinGame => join_game
+        leaveGame => leave_game
+        cancelGame => cancel_game
         claimBackWager => claim_back_wager
+        emergencyWithdraw => emergency_withdraw
+        disputeResult => dispute_result
+        finalizeResult => finalize_result
+        claimRewards => claim_rewards
+        getGameStatus => game_status
         getTokenId => token_id
         getGameStartFee => game_start_fee
+        getAcceptedTokens => accepted_tokens
+        getTokenMinWager => token_min_wager
+        getTokenMaxWager => token_max_wager
+        getTokenStartFee => token_start_fee
+        getRake => rake
+        getLeavePenalty => leave_penalty
+        getForfeitStartFeeOnCancel => forfeit_start_fee_on_cancel
+        getTreasury => treasury
+        getCollectedFees => collected_fees
+        getMinWaitingTime => min_waiting_time
+        getMaxWaitingTime => max_waiting_time
+        getMinPlayers => min_players
+        getMaxPlayers => max_players
+        getMinScheduleLead => min_schedule_lead
+        getMaxScheduleLead => max_schedule_lead
+        getSettlementTimeout => settlement_timeout
+        getSettlementBatchSize => settlement_batch_size
+        getPullPayouts => pull_payouts
+        getQuorumThreshold => quorum_threshold
+        getRequiredApprovals => required_approvals
+        getProposalDuration => proposal_duration
+        getChallengePeriod => challenge_period
+        getResultSigner => result_signer
+        getStorageVersion => storage_version
         getEnabled => enabled
+        isEmergencyMode => emergency_mode
+        getEmergencyTimeout => emergency_timeout
+        isOperationPaused => paused
         isUserAdmin => is_user_admin
         getLastGameId => last_game_id
         getGameSettings => game_settings
-        getGameIdBySettings => game_id
+        getGamesByCreator => games_by_creator
+        getLegacyLastGameId => legacy_last_game_id
+        getLegacyMigratedGameId => legacy_migrated_game_id
+        getOpenGameIds => open_games
+        getSettlement => settlement
+        getPendingResult => pending_result
+        getSettlementProposal => settlement_proposal
+        getSignedResultNonce => signed_result_nonce
+        getGameResult => game_result
+        getGameEscrow => game_escrow
         getPlayers => players
+        getClaimable => claimable
         getGamesPerUser => games_per_user
         sendReward => send_reward
+        approveSettlement => approve_settlement
+        executeSettlement => execute_settlement
+        expireSettlement => expire_settlement
+        submitSignedResult => submit_signed_result
+        resolveDispute => resolve_dispute
+        continueSettlement => continue_settlement
+        migrateLegacyGames => migrate_legacy_games
         enableSC => enable_sc
         disableSC => disable_sc
+        pauseOperation => pause_operation
+        unpauseOperation => unpause_operation
         setTokenId => set_token_id
+        addAcceptedToken => add_accepted_token
+        removeAcceptedToken => remove_accepted_token
+        setTokenMinWager => set_token_min_wager
+        setTokenMaxWager => set_token_max_wager
+        setWaitingTimeLimits => set_waiting_time_limits
+        setPlayerLimits => set_player_limits
+        setTokenStartFee => set_token_start_fee
         setGameStartFee => set_game_start_fee
+        setRake => set_rake
+        setLeavePenalty => set_leave_penalty
+        setForfeitStartFeeOnCancel => set_forfeit_start_fee_on_cancel
+        setScheduleBounds => set_schedule_bounds
+        setSettlementTimeout => set_settlement_timeout
+        setSettlementBatchSize => set_settlement_batch_size
+        setPullPayouts => set_pull_payouts
+        setQuorumThreshold => set_quorum_threshold
+        setRequiredApprovals => set_required_approvals
+        setProposalDuration => set_proposal_duration
+        setEmergencyMode => set_emergency_mode
+        setEmergencyTimeout => set_emergency_timeout
+        setChallengePeriod => set_challenge_period
+        setResultSigner => set_result_signer
+        withdrawRake => withdraw_rake
+        withdrawFees => withdraw_fees
         setAdmin => set_admin
         removeAdmin => remove_admin
+        grantRole => grant_role
+        revokeRole => revoke_role
+        getGameIdByCreator => get_game_id_by_creator
+        getGameInfo => get_game_info
+        getGamesInfo => get_games_info
+        getOpenGames => get_open_games
+        getGames => get_games
+        getPauseMatrix => get_pause_matrix
+        getGameLimits => game_limits
+        getRoles => get_roles
+        getPendingProposals => get_pending_proposals
+        getProposalApprovers => get_proposal_approvers
     )
 }
 